@@ -0,0 +1,166 @@
+use proc_macro2::Span;
+use std::path::Path;
+use syn::{Error, Ident};
+
+/// Access rights of the current user, which can be required with `readable`, `writable` or `executable`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Access {
+    Read,
+    Write,
+    Execute,
+}
+
+impl Access {
+    pub(crate) fn from_ident(ident: &Ident) -> Option<Self> {
+        match ident.to_string().as_str() {
+            "readable" => Some(Self::Read),
+            "writable" => Some(Self::Write),
+            "executable" => Some(Self::Execute),
+            _ => None,
+        }
+    }
+
+    fn verb(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write to",
+            Self::Execute => "execute",
+        }
+    }
+}
+
+/// Checks the access right on an existing path. A missing path is checked to be creatable instead.
+pub(crate) fn try_access(path: &Path, access: Access, span: Span) -> syn::Result<()> {
+    if path.symlink_metadata().is_err() {
+        return match access {
+            Access::Write => try_creatable(path, span),
+            // Reading or executing a missing path is reported by `exists`.
+            Access::Read | Access::Execute => Ok(()),
+        };
+    }
+    check(path, access).map_err(|e| describe(path, access, e, span))?;
+    if access == Access::Write {
+        try_sticky(path, span)?;
+    }
+    Ok(())
+}
+
+/// Checks, that the nearest existing ancestor of a missing path allows creating entries in it.
+pub(crate) fn try_creatable(path: &Path, span: Span) -> syn::Result<()> {
+    let Some(ancestor) = path
+        .ancestors()
+        .skip(1)
+        .map(|ancestor| match ancestor.as_os_str().is_empty() {
+            true => Path::new("."),
+            false => ancestor,
+        })
+        .find(|ancestor| crate::cache::lookup(ancestor) == crate::cache::Lookup::Dir)
+    else {
+        return Ok(());
+    };
+    // Creating an entry requires searching and writing the directory.
+    for access in [Access::Execute, Access::Write] {
+        check(ancestor, access).map_err(|e| describe(ancestor, access, e, span))?;
+    }
+    Ok(())
+}
+
+fn describe(path: &Path, access: Access, e: std::io::Error, span: Span) -> Error {
+    #[cfg(unix)]
+    if e.raw_os_error() == Some(libc::EROFS) {
+        return Error::new(
+            span,
+            format!("\"{}\" is on a read-only filesystem", path.display()),
+        );
+    }
+    if e.kind() == std::io::ErrorKind::PermissionDenied {
+        Error::new(
+            span,
+            format!(
+                "the current user can't {} \"{}\"",
+                access.verb(),
+                path.display()
+            ),
+        )
+    } else {
+        Error::new(
+            span,
+            format!("can't check access to \"{}\": {e}", path.display()),
+        )
+    }
+}
+
+/// Asks the OS, if the effective user has the access right.
+#[cfg(unix)]
+fn check(path: &Path, access: Access) -> std::io::Result<()> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    let mode = match access {
+        Access::Read => libc::R_OK,
+        Access::Write => libc::W_OK,
+        Access::Execute => libc::X_OK,
+    };
+    // SAFETY: `path` is a valid NUL-terminated string, which outlives the call.
+    let result = unsafe { libc::faccessat(libc::AT_FDCWD, path.as_ptr(), mode, libc::AT_EACCESS) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+/// Without `access(2)` only the read-only attribute and opening for reading can be checked.
+#[cfg(not(unix))]
+fn check(path: &Path, access: Access) -> std::io::Result<()> {
+    match access {
+        Access::Read if path.is_file() => std::fs::File::open(path).map(drop),
+        Access::Write if path.metadata()?.permissions().readonly() => {
+            Err(std::io::ErrorKind::PermissionDenied.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Creating over an existing file in a world-writable sticky directory (e.g. `/tmp`) is refused, if the file
+/// belongs to neither the current user nor the owner of the directory (`fs.protected_regular`).
+#[cfg(target_os = "linux")]
+fn try_sticky(path: &Path, span: Span) -> syn::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let (Some(parent), Ok(metadata)) = (path.parent(), path.symlink_metadata()) else {
+        return Ok(());
+    };
+    let parent = match parent.as_os_str().is_empty() {
+        true => Path::new("."),
+        false => parent,
+    };
+    let Ok(parent_metadata) = parent.metadata() else {
+        return Ok(());
+    };
+    let sticky = parent_metadata.mode() & libc::S_ISVTX != 0;
+    let world_writable = parent_metadata.mode() & libc::S_IWOTH != 0;
+    // SAFETY: `geteuid` has no preconditions and can't fail.
+    let euid = unsafe { libc::geteuid() };
+    if metadata.is_file()
+        && sticky
+        && world_writable
+        && metadata.uid() != euid
+        && metadata.uid() != parent_metadata.uid()
+    {
+        return Err(Error::new(
+            span,
+            format!(
+                "\"{}\" belongs to another user in the sticky directory \"{}\", so it can't be created over",
+                path.display(),
+                parent.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn try_sticky(_path: &Path, _span: Span) -> syn::Result<()> {
+    Ok(())
+}
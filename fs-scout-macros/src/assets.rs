@@ -0,0 +1,71 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::path::Path;
+use syn::Error;
+
+use crate::{
+    digest::{to_hex, Algorithm},
+    walk,
+};
+
+/// Number of hexadecimal characters of the content hash inserted into file names.
+const HASH_LEN: usize = 8;
+
+/// Builds a sorted slice of `(logical path, hashed path, bytes or path)` for every file in `dir`.
+pub(crate) fn manifest(dir: &Path, embed: bool, span: Span) -> syn::Result<TokenStream> {
+    if !dir.is_dir() {
+        return Err(Error::new(
+            span,
+            format!("\"{}\" is not a directory", dir.display()),
+        ));
+    }
+    let files = walk::files(dir).map_err(|e| {
+        Error::new(
+            span,
+            format!("can't list files in \"{}\": {e}", dir.display()),
+        )
+    })?;
+
+    let mut assets = vec![];
+    for file in files {
+        #[cfg(feature = "win")]
+        for name in file.relative.split('/') {
+            crate::check_name(name, span)
+                .map_err(|e| Error::new(span, format!("asset \"{}\": {e}", file.relative)))?;
+        }
+
+        let contents = std::fs::read(&file.path).map_err(|e| {
+            Error::new(span, format!("can't read \"{}\": {e}", file.path.display()))
+        })?;
+        let hash = to_hex(&Algorithm::Sha256.digest(&contents));
+        let hashed = hashed_name(&file.relative, &hash[..HASH_LEN]);
+        let logical = &file.relative;
+        let absolute = crate::absolute(&file.path).display().to_string();
+
+        assets.push(if embed {
+            quote! { (#logical, #hashed, ::core::include_bytes!(#absolute) as &[u8]) }
+        } else {
+            let path = file.path.display().to_string();
+            quote! {
+                {
+                    const _: &[u8] = ::core::include_bytes!(#absolute);
+                    (#logical, #hashed, #path)
+                }
+            }
+        });
+    }
+
+    Ok(quote! { &[#(#assets),*] })
+}
+
+/// Inserts the hash before the last extension, e.g. `js/app.js` becomes `js/app.3f9a1c2b.js`.
+fn hashed_name(logical: &str, hash: &str) -> String {
+    let (dir, name) = match logical.rfind('/') {
+        Some(i) => logical.split_at(i + 1),
+        None => ("", logical),
+    };
+    match name.rfind('.') {
+        Some(i) if i > 0 => format!("{dir}{}.{hash}{}", &name[..i], &name[i..]),
+        _ => format!("{dir}{name}.{hash}"),
+    }
+}
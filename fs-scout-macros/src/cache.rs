@@ -0,0 +1,186 @@
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::snapshot::Snapshot;
+
+/// What a path refers to, following symlinks.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Lookup {
+    /// Doesn't exist or can't be accessed.
+    Missing,
+    Dir,
+    File,
+    Other,
+}
+
+/// Lookups shared between the invocations of one compilation.
+///
+/// The compiler loads the macros once per crate, so statics live as long as its compilation.
+/// Long-running hosts like rust-analyzer keep them loaded though, while files change between
+/// expansions of the same crate. So the cache is reset, when another crate is expanded, and the
+/// state of the filesystem is dropped, when the cache wasn't used for [`MAX_IDLE`]. A compilation
+/// expands its macros in one go, so it rarely idles that long. If it does, paths are just looked
+/// up again, while the lexical note stays emitted once.
+struct Cache {
+    compilation: Option<OsString>,
+    last_used: Instant,
+    current_dir: Option<PathBuf>,
+    lookups: HashMap<PathBuf, Lookup>,
+    lexical_noted: bool,
+    snapshot: Option<Arc<Snapshot>>,
+}
+
+const MAX_IDLE: Duration = Duration::from_secs(1);
+
+static CACHE: Mutex<Option<Cache>> = Mutex::new(None);
+
+fn with_cache<T>(f: impl FnOnce(&mut Cache) -> T) -> T {
+    let compilation = std::env::var_os("CARGO_MANIFEST_DIR").map(|mut compilation| {
+        compilation.push(std::env::var_os("CARGO_CRATE_NAME").unwrap_or_default());
+        compilation
+    });
+    let now = Instant::now();
+    let mut guard = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = match guard.as_mut() {
+        Some(cache) => {
+            cache.refresh(compilation, now);
+            cache
+        }
+        None => guard.insert(Cache::new(compilation, now)),
+    };
+    f(cache)
+}
+
+impl Cache {
+    fn new(compilation: Option<OsString>, now: Instant) -> Self {
+        Self {
+            compilation,
+            last_used: now,
+            current_dir: None,
+            lookups: HashMap::new(),
+            lexical_noted: false,
+            snapshot: None,
+        }
+    }
+
+    fn refresh(&mut self, compilation: Option<OsString>, now: Instant) {
+        if self.compilation != compilation {
+            *self = Self::new(compilation, now);
+            return;
+        }
+        if now.saturating_duration_since(self.last_used) >= MAX_IDLE {
+            self.current_dir = None;
+            self.lookups.clear();
+            self.snapshot = None;
+        }
+        self.last_used = now;
+    }
+
+    fn lookup(&mut self, path: &Path) -> Lookup {
+        *self
+            .lookups
+            .entry(path.to_path_buf())
+            .or_insert_with(|| match path.metadata() {
+                Err(_) => Lookup::Missing,
+                Ok(metadata) if metadata.is_dir() => Lookup::Dir,
+                Ok(metadata) if metadata.is_file() => Lookup::File,
+                Ok(_) => Lookup::Other,
+            })
+    }
+
+    fn first_lexical_note(&mut self) -> bool {
+        !std::mem::replace(&mut self.lexical_noted, true)
+    }
+}
+
+pub(crate) fn current_dir() -> PathBuf {
+    with_cache(|cache| {
+        cache
+            .current_dir
+            .get_or_insert_with(|| std::env::current_dir().expect("current dir should be valid"))
+            .clone()
+    })
+}
+
+/// Looks up, what a path refers to. Meant for ancestors of scouted paths, which are shared by many of them.
+pub(crate) fn lookup(path: &Path) -> Lookup {
+    with_cache(|cache| cache.lookup(path))
+}
+
+/// Returns `true` only for the first call, so the lexical mode is noted once instead of for every path.
+pub(crate) fn first_lexical_note() -> bool {
+    with_cache(Cache::first_lexical_note)
+}
+
+/// Returns the loaded snapshot or loads it. Failures aren't cached, so they are reported for every path.
+pub(crate) fn snapshot(
+    load: impl FnOnce() -> Result<Snapshot, String>,
+) -> Result<Arc<Snapshot>, String> {
+    with_cache(|cache| match &cache.snapshot {
+        Some(snapshot) => Ok(Arc::clone(snapshot)),
+        None => Ok(Arc::clone(cache.snapshot.insert(Arc::new(load()?)))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cache, Lookup, MAX_IDLE};
+    use std::{ffi::OsString, path::Path, time::Instant};
+
+    fn compilation(name: &str) -> Option<OsString> {
+        Some(name.into())
+    }
+
+    fn cached(cache: &Cache, path: &Path) -> Option<Lookup> {
+        cache.lookups.get(path).copied()
+    }
+
+    #[test]
+    fn lookups() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let start = Instant::now();
+        let mut cache = Cache::new(compilation("a"), start);
+        assert!(cache.lookup(path) == Lookup::Dir);
+        assert!(cache.lookup(&path.join("missing")) == Lookup::Missing);
+        assert!(cache.lookup(&path.join("Cargo.toml")) == Lookup::File);
+
+        // Used steadily, the cache outlives `MAX_IDLE`.
+        for i in 1..=4 {
+            cache.refresh(compilation("a"), start + MAX_IDLE / 2 * i);
+            assert!(cached(&cache, path) == Some(Lookup::Dir));
+        }
+        cache.refresh(compilation("a"), start + MAX_IDLE * 4);
+        assert!(cached(&cache, path).is_none());
+    }
+
+    #[test]
+    fn lexical_note() {
+        let start = Instant::now();
+        let mut cache = Cache::new(compilation("a"), start);
+        assert!(cache.first_lexical_note());
+        assert!(!cache.first_lexical_note());
+
+        // An idle compilation only looks paths up again.
+        cache.refresh(compilation("a"), start + MAX_IDLE * 2);
+        assert!(!cache.first_lexical_note());
+
+        // Another crate is noted again.
+        cache.refresh(compilation("b"), start + MAX_IDLE * 2);
+        assert!(cache.first_lexical_note());
+    }
+
+    #[test]
+    fn other_compilation() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let start = Instant::now();
+        let mut cache = Cache::new(compilation("a"), start);
+        cache.lookup(path);
+        cache.refresh(compilation("b"), start);
+        assert!(cached(&cache, path).is_none());
+    }
+}
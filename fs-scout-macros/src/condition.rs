@@ -0,0 +1,150 @@
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Error, Ident, LitStr, Token,
+};
+
+/// Environment variable listing the active features of the invoking crate, separated by commas.
+/// Set by `fs_scout::build::export_features` from a build script.
+pub(crate) const FEATURES_VAR: &str = "FS_SCOUT_FEATURES";
+
+/// A condition given with `when = ...`, e.g. `when = all(feature("a"), not(feature("b")))`.
+pub(crate) enum Condition {
+    Feature(LitStr),
+    Not(Box<Condition>),
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
+
+impl Parse for Condition {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse::<Ident>()?;
+        let content;
+        parenthesized!(content in input);
+        let list = |content: ParseStream| {
+            Punctuated::<Condition, Token![,]>::parse_terminated(content)
+                .map(|conditions| conditions.into_iter().collect())
+        };
+        let condition = match key.to_string().as_str() {
+            "feature" => Self::Feature(content.parse()?),
+            "not" => Self::Not(Box::new(content.parse()?)),
+            "all" => Self::All(list(&content)?),
+            "any" => Self::Any(list(&content)?),
+            other => {
+                return Err(Error::new(
+                    key.span(),
+                    format!("unknown condition `{other}`, expected one of: feature, not, all, any"),
+                ))
+            }
+        };
+        if !content.is_empty() {
+            return Err(content.error("unexpected tokens in condition"));
+        }
+        Ok(condition)
+    }
+}
+
+impl Condition {
+    /// Evaluates the condition against the features exported by the build script.
+    pub(crate) fn eval(&self) -> syn::Result<bool> {
+        match self {
+            Self::Feature(name) => {
+                let features = std::env::var(FEATURES_VAR).map_err(|_| {
+                    Error::new(
+                        name.span(),
+                        format!(
+                            "the active features are unknown, as {FEATURES_VAR} is not set; \
+                             call `fs_scout::build::export_features()` from the build script"
+                        ),
+                    )
+                })?;
+                let name = normalize(&name.value());
+                Ok(features
+                    .split(',')
+                    .any(|feature| normalize(feature) == name))
+            }
+            Self::Not(condition) => Ok(!condition.eval()?),
+            Self::All(conditions) => {
+                for condition in conditions {
+                    if !condition.eval()? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Self::Any(conditions) => {
+                for condition in conditions {
+                    if condition.eval()? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+        }
+    }
+}
+
+/// Cargo exposes features to build scripts as `CARGO_FEATURE_<NAME>`, which loses case and dashes.
+fn normalize(feature: &str) -> String {
+    feature.trim().to_lowercase().replace('-', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Condition, FEATURES_VAR};
+
+    fn eval(condition: &str) -> Result<bool, String> {
+        syn::parse_str::<Condition>(condition)
+            .and_then(|condition| condition.eval())
+            .map_err(|e| e.to_string())
+    }
+
+    // A single test, as the variable is shared by the whole process.
+    #[test]
+    fn evaluation() {
+        std::env::remove_var(FEATURES_VAR);
+        assert!(eval("feature(\"bundled\")")
+            .unwrap_err()
+            .starts_with("the active features are unknown"));
+
+        std::env::set_var(FEATURES_VAR, "bundled_models,Default");
+        for (condition, expected) in [
+            ("feature(\"bundled_models\")", true),
+            ("feature(\"bundled-models\")", true),
+            ("feature(\"default\")", true),
+            ("feature(\"bundled\")", false),
+            ("not(feature(\"bundled\"))", true),
+            ("all(feature(\"default\"), not(feature(\"bundled\")))", true),
+            ("all(feature(\"default\"), feature(\"bundled\"))", false),
+            ("all()", true),
+            (
+                "any(feature(\"bundled\"), feature(\"Bundled-Models\"))",
+                true,
+            ),
+            ("any(feature(\"bundled\"))", false),
+            ("any()", false),
+        ] {
+            assert_eq!(eval(condition), Ok(expected), "{condition}");
+        }
+        std::env::set_var(FEATURES_VAR, "");
+        assert_eq!(eval("feature(\"default\")"), Ok(false));
+        std::env::remove_var(FEATURES_VAR);
+    }
+
+    #[test]
+    fn invalid_conditions() {
+        assert!(syn::parse_str::<Condition>("cfg(test)")
+            .err()
+            .unwrap()
+            .to_string()
+            .starts_with("unknown condition `cfg`"));
+        assert_eq!(
+            syn::parse_str::<Condition>("not(feature(\"a\"), feature(\"b\"))")
+                .err()
+                .unwrap()
+                .to_string(),
+            "unexpected tokens in condition"
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use proc_macro2::Span;
+use sha2::{Digest, Sha256};
+use syn::{Error, Ident, LitStr};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Algorithm {
+    Sha256,
+    Blake3,
+    Crc32,
+}
+
+impl Algorithm {
+    pub(crate) fn from_ident(ident: &Ident) -> Option<Self> {
+        match ident.to_string().as_str() {
+            "sha256" => Some(Self::Sha256),
+            "blake3" => Some(Self::Blake3),
+            "crc32" => Some(Self::Crc32),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Blake3 => "blake3",
+            Self::Crc32 => "crc32",
+        }
+    }
+
+    /// Number of bytes in a digest. Crc32 is represented as its big-endian bytes.
+    pub(crate) fn size(self) -> usize {
+        match self {
+            Self::Sha256 | Self::Blake3 => 32,
+            Self::Crc32 => 4,
+        }
+    }
+
+    pub(crate) fn digest(self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => Sha256::digest(bytes).to_vec(),
+            Self::Blake3 => blake3::hash(bytes).as_bytes().to_vec(),
+            Self::Crc32 => crc32fast::hash(bytes).to_be_bytes().to_vec(),
+        }
+    }
+}
+
+/// A digest, the referenced file is pinned to.
+///
+/// Without an expected value, verification always fails and reports the actual digest,
+/// which is meant as a helper for pinning a file for the first time.
+pub(crate) struct Pin {
+    pub(crate) algorithm: Algorithm,
+    pub(crate) expected: Option<LitStr>,
+    pub(crate) span: Span,
+}
+
+impl Pin {
+    pub(crate) fn new(
+        algorithm: Algorithm,
+        expected: Option<LitStr>,
+        span: Span,
+    ) -> syn::Result<Self> {
+        if let Some(expected) = &expected {
+            let value = expected.value();
+            if value.len() != algorithm.size() * 2 || !value.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return Err(Error::new(
+                    expected.span(),
+                    format!(
+                        "{} digest should be {} hexadecimal characters",
+                        algorithm.name(),
+                        algorithm.size() * 2
+                    ),
+                ));
+            }
+        }
+        Ok(Self {
+            algorithm,
+            expected,
+            span,
+        })
+    }
+
+    pub(crate) fn verify(&self, path: &str, contents: &[u8]) -> syn::Result<()> {
+        let actual = to_hex(&self.algorithm.digest(contents));
+        match &self.expected {
+            None => Err(Error::new(
+                self.span,
+                format!(
+                    "{} digest of \"{}\" is \"{}\"; pin it with `{} = \"{}\"`",
+                    self.algorithm.name(),
+                    path,
+                    actual,
+                    self.algorithm.name(),
+                    actual
+                ),
+            )),
+            Some(expected) if expected.value().eq_ignore_ascii_case(&actual) => Ok(()),
+            Some(expected) => Err(Error::new(
+                expected.span(),
+                format!(
+                    "{} mismatch for \"{}\": expected \"{}\", actual \"{}\"",
+                    self.algorithm.name(),
+                    path,
+                    expected.value(),
+                    actual
+                ),
+            )),
+        }
+    }
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,294 @@
+use proc_macro2::Span;
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Error, LitInt, LitStr, Token,
+};
+
+use crate::{cache::Lookup, snapshot::Snapshot, walk};
+
+/// What a directory is expected to contain.
+pub(crate) enum Expected {
+    /// Entries, whose names match a glob pattern.
+    Pattern(LitStr),
+    /// Entries with exactly these names.
+    Names(Vec<LitStr>),
+}
+
+impl Parse for Expected {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(LitStr) {
+            Ok(Self::Pattern(input.parse()?))
+        } else if lookahead.peek(syn::token::Bracket) {
+            let content;
+            bracketed!(content in input);
+            let names = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+            Ok(Self::Names(names.into_iter().collect()))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// Limits and flags accepted by `dir_contains`.
+#[derive(Default)]
+pub(crate) struct Bounds {
+    pub(crate) min: Option<(usize, Span)>,
+    pub(crate) max: Option<(usize, Span)>,
+    /// Fails on entries, which aren't expected.
+    pub(crate) only: bool,
+}
+
+impl Bounds {
+    /// Parses a single option. Returns `false` for keys, which don't belong to `dir_contains`.
+    pub(crate) fn parse_option(
+        &mut self,
+        key: &syn::Ident,
+        input: ParseStream,
+    ) -> syn::Result<bool> {
+        if key == "only" {
+            if self.only {
+                return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+            }
+            self.only = true;
+        } else if key == "min" || key == "max" {
+            let bound = if key == "min" {
+                &mut self.min
+            } else {
+                &mut self.max
+            };
+            if bound.is_some() {
+                return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+            }
+            input.parse::<Token![=]>()?;
+            let value = input.parse::<LitInt>()?;
+            *bound = Some((value.base10_parse()?, value.span()));
+            if let (Some((min, _)), Some((max, _))) = (self.min, self.max) {
+                if min > max {
+                    return Err(Error::new(
+                        value.span(),
+                        format!("`min` of {min} exceeds `max` of {max}"),
+                    ));
+                }
+            }
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+}
+
+/// Checks the direct children of a directory on the live disk.
+pub(crate) fn try_contains(
+    dir: &Path,
+    expected: &Expected,
+    bounds: &Bounds,
+    span: Span,
+) -> syn::Result<()> {
+    if !dir.is_dir() {
+        return Err(Error::new(
+            span,
+            format!("\"{}\" is not a directory", dir.display()),
+        ));
+    }
+    let mut names = dir
+        .read_dir()
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|e| e.file_name().to_string_lossy().into_owned()))
+                .collect::<std::io::Result<Vec<_>>>()
+        })
+        .map_err(|e| {
+            Error::new(
+                span,
+                format!("can't list entries of \"{}\": {e}", dir.display()),
+            )
+        })?;
+    names.sort();
+    try_names(dir, &names, expected, bounds, span)
+}
+
+/// Checks the direct children of a directory recorded in a snapshot.
+pub(crate) fn try_contains_recorded(
+    snapshot: &Snapshot,
+    dir: &Path,
+    expected: &Expected,
+    bounds: &Bounds,
+    span: Span,
+) -> syn::Result<()> {
+    if snapshot.lookup(dir) != Lookup::Dir {
+        return Err(Error::new(
+            span,
+            format!("\"{}\" is not a directory in the snapshot", dir.display()),
+        ));
+    }
+    try_names(dir, &snapshot.children(dir), expected, bounds, span)
+}
+
+/// Checks the sorted names of the children of `dir`.
+fn try_names(
+    dir: &Path,
+    names: &[String],
+    expected: &Expected,
+    bounds: &Bounds,
+    span: Span,
+) -> syn::Result<()> {
+    let mut errors = vec![];
+    let unexpected = match expected {
+        Expected::Pattern(pattern) => {
+            let glob = glob::Pattern::new(&pattern.value())
+                .map_err(|e| Error::new(pattern.span(), format!("invalid glob pattern: {e}")))?;
+            let (matching, unexpected): (Vec<_>, Vec<_>) =
+                names.iter().partition(|name| glob.matches(name));
+
+            // Without a limit, the pattern has to match at least once.
+            let default_min = if bounds.max.is_some() { 0 } else { 1 };
+            let (min, min_span) = bounds.min.unwrap_or((default_min, pattern.span()));
+            if matching.len() < min {
+                errors.push(Error::new(
+                    min_span,
+                    format!(
+                        "\"{}\" contains {} entries matching \"{}\", expected at least {min}",
+                        dir.display(),
+                        matching.len(),
+                        pattern.value()
+                    ),
+                ));
+            }
+            if let Some((max, max_span)) = bounds.max {
+                if matching.len() > max {
+                    errors.push(Error::new(
+                        max_span,
+                        format!(
+                            "\"{}\" contains {} entries matching \"{}\", expected at most {max}",
+                            dir.display(),
+                            matching.len(),
+                            pattern.value()
+                        ),
+                    ));
+                }
+            }
+            unexpected
+        }
+        Expected::Names(expected) => {
+            if let Some((_, span)) = bounds.min.or(bounds.max) {
+                return Err(Error::new(
+                    span,
+                    "`min` and `max` are only supported with a pattern",
+                ));
+            }
+            for name in expected {
+                if !names.contains(&name.value()) {
+                    errors.push(Error::new(
+                        name.span(),
+                        format!("\"{}\" doesn't contain \"{}\"", dir.display(), name.value()),
+                    ));
+                }
+            }
+            names
+                .iter()
+                .filter(|name| !expected.iter().any(|e| e.value() == **name))
+                .collect()
+        }
+    };
+
+    if bounds.only && !unexpected.is_empty() {
+        errors.push(Error::new(
+            span,
+            format!(
+                "\"{}\" contains unexpected entries: {}",
+                dir.display(),
+                unexpected
+                    .iter()
+                    .map(|name| format!("\"{name}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ));
+    }
+
+    crate::combine_errors(errors)
+}
+
+/// Checks, that all directories contain the same files (compared by their relative paths).
+///
+/// Without a reference, every directory has to contain the files of all others.
+/// With a reference, files missing from or extra to the reference directory are reported.
+pub(crate) fn try_consistent(
+    dirs: &[PathBuf],
+    reference: Option<&Path>,
+    span: Span,
+) -> syn::Result<()> {
+    let mut trees = vec![];
+    for dir in dirs {
+        let files = walk::files(dir).map_err(|e| {
+            Error::new(
+                span,
+                format!("can't list files in \"{}\": {e}", dir.display()),
+            )
+        })?;
+        let files = files
+            .into_iter()
+            .map(|file| file.relative)
+            .collect::<BTreeSet<_>>();
+        #[cfg(feature = "win")]
+        for file in &files {
+            for name in file.split('/') {
+                crate::check_name(name, span)
+                    .map_err(|e| Error::new(span, format!("\"{}/{file}\": {e}", dir.display())))?;
+            }
+        }
+        trees.push(files);
+    }
+
+    let expected = match reference {
+        Some(reference) => {
+            let i = dirs
+                .iter()
+                .position(|dir| dir == reference)
+                .ok_or_else(|| {
+                    Error::new(
+                        span,
+                        format!(
+                            "reference \"{}\" is not one of the matched directories",
+                            reference.display()
+                        ),
+                    )
+                })?;
+            trees[i].clone()
+        }
+        None => trees.iter().flatten().cloned().collect(),
+    };
+
+    let list = |files: Vec<&String>| {
+        files
+            .iter()
+            .map(|file| format!("\"{file}\""))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut errors = vec![];
+    for (dir, files) in dirs.iter().zip(&trees) {
+        let missing = expected.difference(files).collect::<Vec<_>>();
+        if !missing.is_empty() {
+            errors.push(Error::new(
+                span,
+                format!("\"{}\" is missing {}", dir.display(), list(missing)),
+            ));
+        }
+        let extra = files.difference(&expected).collect::<Vec<_>>();
+        if !extra.is_empty() {
+            errors.push(Error::new(
+                span,
+                format!("\"{}\" has extra {}", dir.display(), list(extra)),
+            ));
+        }
+    }
+    crate::combine_errors(errors)
+}
@@ -0,0 +1,91 @@
+use proc_macro2::Span;
+use std::{fs::FileType, path::Path};
+use syn::{Error, Ident};
+
+/// Type of a filesystem entry, given explicitly before the path, e.g. `exists!(dir "v1.2")`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EntryKind {
+    File,
+    Dir,
+    Symlink,
+    Fifo,
+    Socket,
+}
+
+impl EntryKind {
+    pub(crate) fn from_ident(ident: &Ident) -> syn::Result<Self> {
+        Ok(match ident.to_string().as_str() {
+            "file" => Self::File,
+            "dir" => Self::Dir,
+            "symlink" => Self::Symlink,
+            "fifo" => Self::Fifo,
+            "socket" => Self::Socket,
+            other => {
+                return Err(Error::new(
+                    ident.span(),
+                    format!(
+                        "unknown entry kind `{other}`, expected one of: file, dir, symlink, fifo, socket"
+                    ),
+                ))
+            }
+        })
+    }
+
+    pub(crate) fn describe(kind: Option<Self>) -> &'static str {
+        match kind {
+            Some(Self::File) => "a regular file",
+            Some(Self::Dir) => "a directory",
+            Some(Self::Symlink) => "a symlink",
+            Some(Self::Fifo) => "a FIFO",
+            Some(Self::Socket) => "a socket",
+            None => "an entry of another kind",
+        }
+    }
+
+    fn of(file_type: FileType) -> Option<Self> {
+        if file_type.is_file() {
+            return Some(Self::File);
+        } else if file_type.is_dir() {
+            return Some(Self::Dir);
+        } else if file_type.is_symlink() {
+            return Some(Self::Symlink);
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+            if file_type.is_fifo() {
+                return Some(Self::Fifo);
+            } else if file_type.is_socket() {
+                return Some(Self::Socket);
+            }
+        }
+        None
+    }
+}
+
+/// Checks the kind of an existing entry against its annotation. Missing entries pass.
+///
+/// Symlinks are followed, unless the annotation is `symlink` itself.
+pub(crate) fn try_entry(path: &Path, expected: EntryKind, span: Span) -> syn::Result<()> {
+    let metadata = match expected {
+        EntryKind::Symlink => path.symlink_metadata(),
+        _ => path.metadata(),
+    };
+    let Ok(metadata) = metadata else {
+        return Ok(());
+    };
+    let actual = EntryKind::of(metadata.file_type());
+    if actual == Some(expected) {
+        Ok(())
+    } else {
+        Err(Error::new(
+            span,
+            format!(
+                "\"{}\" is {}, expected {}",
+                path.display(),
+                EntryKind::describe(actual),
+                EntryKind::describe(Some(expected))
+            ),
+        ))
+    }
+}
@@ -0,0 +1,136 @@
+use proc_macro2::Span;
+use std::path::Path;
+use syn::{Error, Ident};
+
+/// Structured formats, a file can be checked to parse as. Each is behind a feature of the same name.
+#[derive(Clone, Copy)]
+pub(crate) enum Format {
+    Toml,
+    Json,
+    Yaml,
+    Ron,
+}
+
+/// Location (1-based line and column) and message of a syntax error.
+struct SyntaxError(usize, usize, String);
+
+impl Format {
+    pub(crate) fn from_ident(ident: &Ident) -> syn::Result<Self> {
+        let (format, enabled) = match ident.to_string().as_str() {
+            "toml" => (Self::Toml, cfg!(feature = "toml")),
+            "json" => (Self::Json, cfg!(feature = "json")),
+            "yaml" => (Self::Yaml, cfg!(feature = "yaml")),
+            "ron" => (Self::Ron, cfg!(feature = "ron")),
+            other => {
+                return Err(Error::new(
+                    ident.span(),
+                    format!("unknown format `{other}`, expected one of: toml, json, yaml, ron"),
+                ))
+            }
+        };
+        if enabled {
+            Ok(format)
+        } else {
+            Err(Error::new(
+                ident.span(),
+                format!("format `{ident}` requires the feature \"{ident}\" to be enabled"),
+            ))
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Toml => "toml",
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Ron => "ron",
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn parse(self, text: &str) -> Result<(), SyntaxError> {
+        match self {
+            #[cfg(feature = "toml")]
+            Self::Toml => toml::from_str::<toml::Table>(text).map(drop).map_err(|e| {
+                let (line, column) = e
+                    .span()
+                    .map_or((1, 1), |span| line_column(text, span.start));
+                SyntaxError(line, column, e.message().to_string())
+            }),
+            #[cfg(feature = "json")]
+            Self::Json => serde_json::from_str::<serde_json::Value>(text)
+                .map(drop)
+                .map_err(|e| SyntaxError(e.line(), e.column(), strip_location(e.to_string()))),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => serde_yaml::from_str::<serde_yaml::Value>(text)
+                .map(drop)
+                .map_err(|e| {
+                    let (line, column) = e
+                        .location()
+                        .map_or((1, 1), |location| (location.line(), location.column()));
+                    SyntaxError(line, column, strip_location(e.to_string()))
+                }),
+            #[cfg(feature = "ron")]
+            Self::Ron => ron::from_str::<ron::Value>(text)
+                .map(drop)
+                .map_err(|e| SyntaxError(e.span.start.line, e.span.start.col, e.code.to_string())),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("disabled formats are rejected while parsing options"),
+        }
+    }
+}
+
+pub(crate) fn try_format(
+    path: &Path,
+    format: Format,
+    contents: &[u8],
+    span: Span,
+) -> syn::Result<()> {
+    let text = std::str::from_utf8(contents).map_err(|e| {
+        Error::new(
+            span,
+            format!(
+                "\"{}\" is not valid {}: invalid UTF-8 after byte {}",
+                path.display(),
+                format.name(),
+                e.valid_up_to()
+            ),
+        )
+    })?;
+
+    format
+        .parse(text)
+        .map_err(|SyntaxError(line, column, message)| {
+            Error::new(
+                span,
+                format!(
+                    "\"{}\" is not valid {} at line {line}, column {column}: {message}",
+                    path.display(),
+                    format.name()
+                ),
+            )
+        })
+}
+
+#[cfg(feature = "toml")]
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    (line, column)
+}
+
+/// Removes a trailing " at line X column Y" from a message, as the location is reported separately.
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn strip_location(mut message: String) -> String {
+    if let Some(i) = message.rfind(" at line ") {
+        let location = &message[i + " at line ".len()..];
+        if location
+            .split(" column ")
+            .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        {
+            message.truncate(i);
+        }
+    }
+    message
+}
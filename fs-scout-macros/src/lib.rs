@@ -9,21 +9,124 @@
 //! files or directories forbidden by Windows). It is included in default feature for that very reason.
 //! If you don't plan to deploy to Windows and need to bypass this restriction, you can use flag
 //! `default-features = false` to disable this behaviour.
+//!
+//! The feature flags `"toml"`, `"json"`, `"yaml"` and `"ron"` enable the respective `format = ...` checks.
+//!
+//! # Options
+//! Options follow the path, separated by commas, e.g. `exists!("a.bin", readable, max_size = "1MiB")`.
+//! They're accepted by [`exists`] and every macro scouting paths the same way.
+//!
+//! * `readable`, `writable` and `executable` check the real access rights of the current user.
+//! * `sha256 = "..."`, `blake3 = "..."` or `crc32 = "..."` pin a file to a checksum. The file is hashed
+//!   during compiletime and any mismatch is reported with the actual digest. Writing just the algorithm
+//!   name (e.g. `exists!("a.bin", sha256)`) fails with the digest to pin.
+//! * `min_size` and `max_size` set a size budget, either in bytes (`min_size = 1`) or with a unit
+//!   (`max_size = "50MiB"`). For directories the size of the whole tree is used.
+//! * `kind = ...` verifies the contents of a file as one of `png`, `jpeg`, `gif`, `webp`, `bmp`, `gzip`,
+//!   `bzip2`, `xz`, `zstd`, `zip`, `seven_zip`, `tar`, `elf`, `pe`, `wasm`, `pdf`, `sqlite` or `utf8`.
+//!   Independently of that, a warning is emitted for any existing file, whose leading bytes don't match
+//!   the type implied by its extension.
+//! * `format = toml`, `json`, `yaml` or `ron` checks, that a structured file parses. Each format requires
+//!   the feature of the same name.
+//! * `normalize` expands to the lexically normalized path: `.` components and repeated separators are
+//!   removed and `name/..` is folded, if `name` is an existing directory, which isn't a symlink.
+//!   A warning is emitted, if the normalized path would refer to a different item on another platform.
+//! * `sep = native`, `unix` or `windows` treats both `/` and `\` as separators. The path is scouted with
+//!   the native separator and expands with the chosen one. Without it, a path mixing `/` and `\` emits
+//!   a warning.
+//! * `symlinks = deny` forbids any component to be a symlink and `symlinks = within_root` requires even
+//!   an absolute path to resolve to an item inside the root. Dangling symlinks are always reported.
+//! * `when = feature("name")`, combined with `not(...)`, `all(...)` and `any(...)`, checks paths, which only
+//!   exist with some features of your crate. If the condition doesn't hold, only the lexical checks run.
+//!   The features have to be exported by calling `fs_scout::build::export_features()` from the build script.
+//! * `message = "..."` extends any error with a custom explanation.
+//!
+//! The kind of the entry can be given before the path as one of `file`, `dir`, `symlink`, `fifo` or
+//! `socket` (e.g. `exists!(dir "v1.2")`). It's checked against the metadata of the existing entry.
+//!
+//! A warning is emitted for a literal, which is absolute on one of the checked platforms and relative
+//! on another (e.g. `"/user"` is relative to the current drive on Windows). The platforms are the one
+//! compiling the code and the ones enabled by the `unix` and `windows` profiles. With the `portable` profile,
+//! literals anchored on any of them are errors and with the `unix` profile, any `\` is an error.
+//! Profiles are set per crate with `fs_scout::build::set_profiles`.
+//!
+//! # Environment
+//! Relative paths are confined to the root, which is the package root by default. It can be changed by
+//! setting `FS_SCOUT_ROOT` (e.g. with `fs_scout::build::set_root("..")` in the build script).
+//! A relative path leading outside the root, lexically or through a symlink, fails compilation.
+//!
+//! For builds without the real files (e.g. on docs.rs or in sandboxes), `FS_SCOUT_MODE=lexical` skips every
+//! check touching the filesystem in all validating macros, keeping the naming and confinement rules.
+//! A warning notes once, that existence wasn't verified. It can also be set with `fs_scout::build::set_lexical()`.
+//! Macros, whose expansion depends on the files (e.g. [`embed_file`] or [`first_existing`]), still need them.
+//!
+//! Absolute paths can be checked against a snapshot of the deployment layout instead of the live disk by setting
+//! `FS_SCOUT_SNAPSHOT` to the snapshot file (e.g. with `fs_scout::build::set_snapshot("fs-scout.lock")`),
+//! which is recorded with `fs_scout::snapshot::write`. Only existence, kinds and names are checked then.
+//! With `FS_SCOUT_MODE=diff` a warning is emitted for every such path, which the live disk disagrees about.
 
 extern crate proc_macro;
 
+mod access;
+mod assets;
+mod cache;
+mod condition;
+mod digest;
+mod dirs;
+mod entry;
+mod format;
+mod lint;
+mod magic;
+mod mode;
+mod normalize;
+mod options;
+mod platform;
+mod root;
+mod separator;
+mod sequence;
+mod size;
+mod snapshot;
+mod symlink;
+mod walk;
+#[cfg(feature = "win")]
+mod win;
+
+use access::Access;
+use cache::Lookup;
+use digest::Algorithm;
+use entry::EntryKind;
+use lint::Lint;
+use mode::Mode;
+use options::ScoutOptions;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use quote::quote;
+use quote::{quote, ToTokens};
+use snapshot::Snapshot;
 use std::{
+    io::Read,
     marker::PhantomData,
-    path::{Component, Components, Path},
+    path::{Component, Components, Path, PathBuf},
+};
+use syn::{
+    parse::{Parse, Parser},
+    Error, Ident, LitStr, Token,
 };
-use syn::{parse::Parse, parse_macro_input, Error, LitStr};
+
+/// Like [`syn::parse_macro_input`], but reports combined errors with [`compile_errors`].
+macro_rules! scout_input {
+    ($input:ident as $ty:ty) => {
+        match syn::parse::<$ty>($input) {
+            Ok(data) => data,
+            Err(e) => return compile_errors(e),
+        }
+    };
+}
 
 /// Checks if a path exists during compiletime.
 /// Useful, if your program requires the path to always exist.
-/// For files doesn't care about permissions.
+/// For files doesn't care about permissions, unless `readable`, `writable` or `executable` is given.
+///
+/// Accepts all [options](crate#options), e.g. checksums, size limits or a `when = ...` condition.
 ///
 /// # Examples
 /// ```rust, ignore
@@ -33,6 +136,8 @@ use syn::{parse::Parse, parse_macro_input, Error, LitStr};
 /// # use fs_scout_macros::exists;
 /// #
 /// const HAS_TO_EXIST: &str = exists!("/yes/");
+/// const IS_PINNED: &str = exists!("/yes/golden.bin", crc32 = "0a1b2c3d");
+/// const IS_BUNDLED: &str = exists!("models/large.bin", when = feature("bundled-models"));
 ///
 /// let file = std::fs::File::open(HAS_TO_EXIST).expect("should exist");
 /// #   Ok(())
@@ -40,9 +145,542 @@ use syn::{parse::Parse, parse_macro_input, Error, LitStr};
 /// ```
 #[proc_macro]
 pub fn exists(input: TokenStream) -> TokenStream {
-    let ScoutData(s, ..) = parse_macro_input!(input as ScoutData<ExistsMatcher>);
+    let data = scout_input!(input as ScoutData<ExistsMatcher>);
+    quote! {
+        #data
+    }
+    .into()
+}
+
+/// Expands to the first of several candidate paths, which exists during compiletime.
+///
+/// Fails listing all candidates, if none of them exists. Options following the candidates
+/// are the same as in [`exists`] and apply to the selected path.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::first_existing;
+/// #
+/// const CONFIG: &str = first_existing!("fs-scout.toml", ".config/app.toml", "config/app.toml");
+/// ```
+#[proc_macro]
+pub fn first_existing(input: TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        let mut candidates = vec![parse_literal(input)?];
+        while input.peek(Token![,]) && input.peek2(LitStr) {
+            input.parse::<Token![,]>()?;
+            candidates.push(input.parse::<LitStr>()?);
+        }
+        let options = input.parse::<ScoutOptions>()?;
+
+        let mut existing = None;
+        for candidate in &candidates {
+            if path_exists(
+                Path::new(&scouted_path(candidate, &options)),
+                candidate.span(),
+            )? {
+                existing = Some(candidate);
+                break;
+            }
+        }
+        match existing {
+            Some(candidate) => ScoutData::<ExistsMatcher>::new(candidate, &options),
+            None => Err(options.explain(Error::new(
+                Span::call_site(),
+                format!(
+                    "none of the candidates exist: {}",
+                    candidates
+                        .iter()
+                        .map(|candidate| format!("\"{}\"", candidate.value()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ))),
+        }
+    };
+    let data = match parser.parse(input) {
+        Ok(data) => data,
+        Err(e) => return compile_errors(e),
+    };
+    quote! {
+        #data
+    }
+    .into()
+}
+
+/// Expands to `Some(path)`, if the path exists during compiletime, and to `None` otherwise.
+///
+/// Accepts the same options as [`exists`], which are only checked, if the path exists.
+/// Note, that creating the path later doesn't trigger recompilation.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::maybe_exists;
+/// #
+/// const OVERRIDES: Option<&str> = maybe_exists!("config/local.toml");
+/// ```
+#[proc_macro]
+pub fn maybe_exists(input: TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        let lit = parse_literal(input)?;
+        let options = input.parse::<ScoutOptions>()?;
+        if path_exists(Path::new(&scouted_path(&lit, &options)), lit.span())? {
+            ScoutData::<ExistsMatcher>::new(&lit, &options).map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+    match parser.parse(input) {
+        Ok(Some(data)) => quote! {
+            ::core::option::Option::<&'static str>::Some(#data)
+        }
+        .into(),
+        Ok(None) => {
+            let env = tracked_env();
+            quote! {
+                {
+                    #env
+                    ::core::option::Option::<&'static str>::None
+                }
+            }
+            .into()
+        }
+        Err(e) => compile_errors(e),
+    }
+}
+
+/// Embeds an existing file as `&'static [u8; N]` using [`include_bytes`].
+///
+/// Unlike [`include_bytes`], the path is resolved the same way as in [`exists`] and accepts
+/// the same options (e.g. checksum pinning).
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::embed_file;
+/// #
+/// static GOLDEN: &[u8] = embed_file!("fixtures/golden.bin", sha256 = "...");
+/// ```
+#[proc_macro]
+pub fn embed_file(input: TokenStream) -> TokenStream {
+    let data = scout_input!(input as ScoutData<ExistsMatcher>);
+    let absolute = data.absolute();
+    quote! {
+        {
+            #data;
+            ::core::include_bytes!(#absolute)
+        }
+    }
+    .into()
+}
+
+/// Expands to the size of an existing file in bytes as `u64`.
+///
+/// The path is validated the same way as in [`exists`] and accepts the same options.
+/// Changing the file triggers recompilation.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::file_size;
+/// #
+/// const TOTAL: u64 = file_size!("assets/model.bin");
+/// ```
+#[proc_macro]
+pub fn file_size(input: TokenStream) -> TokenStream {
+    let mut data = scout_input!(input as ScoutData<ExistsMatcher>);
+    let size = match data.track_file() {
+        Ok(metadata) => metadata.len(),
+        Err(e) => return compile_errors(e),
+    };
+    quote! {
+        {
+            #data;
+            #size
+        }
+    }
+    .into()
+}
+
+/// Expands to the last modification time of an existing file as `u64` seconds since the Unix epoch.
+///
+/// The path is validated the same way as in [`exists`] and accepts the same options.
+/// Changing the file triggers recompilation.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::file_mtime;
+/// #
+/// const MODIFIED: u64 = file_mtime!("assets/model.bin");
+/// ```
+#[proc_macro]
+pub fn file_mtime(input: TokenStream) -> TokenStream {
+    let mut data = scout_input!(input as ScoutData<ExistsMatcher>);
+    let mtime = match data.track_file().and_then(|metadata| {
+        metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs())
+            .ok_or_else(|| Error::new(data.span, "modification time is not available"))
+    }) {
+        Ok(mtime) => mtime,
+        Err(e) => return compile_errors(e),
+    };
+    quote! {
+        {
+            #data;
+            #mtime
+        }
+    }
+    .into()
+}
+
+/// Expands to the number of lines in an existing file as `usize`.
+///
+/// A trailing line without a line break is counted as well.
+/// The path is validated the same way as in [`exists`] and accepts the same options.
+/// Changing the file triggers recompilation.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::file_lines;
+/// #
+/// const LINES: usize = file_lines!("data/words.txt");
+/// ```
+#[proc_macro]
+pub fn file_lines(input: TokenStream) -> TokenStream {
+    let mut data = scout_input!(input as ScoutData<ExistsMatcher>);
+    let lines = match data.track_file().and_then(|_| data.read()) {
+        Ok(contents) => {
+            contents.iter().filter(|&&b| b == b'\n').count()
+                + usize::from(contents.last().is_some_and(|&b| b != b'\n'))
+        }
+        Err(e) => return compile_errors(e),
+    };
+    quote! {
+        {
+            #data;
+            #lines
+        }
+    }
+    .into()
+}
+
+/// Expands to the digest of an existing file as a byte array.
+///
+/// The algorithm can be given before the path and is one of `sha256` (default, `[u8; 32]`),
+/// `blake3` (`[u8; 32]`) or `crc32` (`[u8; 4]`, big-endian).
+/// The path is validated the same way as in [`exists`] and accepts the same options.
+/// Changing the file triggers recompilation.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::file_hash;
+/// #
+/// const CACHE_KEY: [u8; 32] = file_hash!("assets/app.js");
+/// const CHECKSUM: [u8; 4] = file_hash!(crc32, "assets/app.js");
+/// ```
+#[proc_macro]
+pub fn file_hash(input: TokenStream) -> TokenStream {
+    let FileHash {
+        algorithm,
+        mut data,
+    } = scout_input!(input as FileHash);
+    let digest = match data.track_file().and_then(|_| data.read()) {
+        Ok(contents) => algorithm.digest(&contents),
+        Err(e) => return compile_errors(e),
+    };
     quote! {
-        #s
+        {
+            #data;
+            [#(#digest),*]
+        }
+    }
+    .into()
+}
+
+struct FileHash {
+    algorithm: Algorithm,
+    data: ScoutData<ExistsMatcher>,
+}
+
+impl Parse for FileHash {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let algorithm = if input.peek(Ident) {
+            let ident = input.parse::<Ident>()?;
+            input.parse::<Token![,]>()?;
+            Algorithm::from_ident(&ident).ok_or_else(|| {
+                Error::new(
+                    ident.span(),
+                    format!("unknown algorithm `{ident}`, expected one of: sha256, blake3, crc32"),
+                )
+            })?
+        } else {
+            Algorithm::Sha256
+        };
+        Ok(Self {
+            algorithm,
+            data: input.parse()?,
+        })
+    }
+}
+
+/// Builds a manifest of content-hashed names for every file in an existing directory tree.
+///
+/// Expands to a slice of `(logical path, hashed path, bytes)` sorted by the logical path,
+/// so it can be searched with [`slice::binary_search_by_key`]. Logical paths are relative to
+/// the directory and always separated with `/`. The hashed path has a part of the file's sha256
+/// inserted before its last extension (e.g. `js/app.js` becomes `js/app.3f9a1c2b.js`).
+///
+/// With `embed = false` the third element is the path of the file on disk instead of its bytes.
+/// If feature `"win"` is enabled, every name in the tree is checked for Windows compatibility.
+/// Symlinked directories inside the tree are skipped, so they can't cause cycles.
+///
+/// Changing a listed file triggers recompilation, adding or removing files doesn't.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::asset_manifest;
+/// #
+/// static ASSETS: &[(&str, &str, &[u8])] = asset_manifest!("static");
+/// static ASSET_PATHS: &[(&str, &str, &str)] = asset_manifest!("static", embed = false);
+///
+/// let i = ASSETS.binary_search_by_key(&"app.js", |&(logical, ..)| logical).unwrap();
+/// let (_, hashed, bytes) = ASSETS[i];
+/// ```
+#[proc_macro]
+pub fn asset_manifest(input: TokenStream) -> TokenStream {
+    let mut embed = None;
+    let data = match (|input: syn::parse::ParseStream| {
+        ScoutData::<ExistsMatcher>::parse_with(input, |key, input| {
+            if key != "embed" {
+                return Ok(false);
+            }
+            if embed.is_some() {
+                return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+            }
+            input.parse::<Token![=]>()?;
+            embed = Some(input.parse::<syn::LitBool>()?.value);
+            Ok(true)
+        })
+    })
+    .parse(input)
+    {
+        Ok(data) => data,
+        Err(e) => return compile_errors(e),
+    };
+    let manifest = match assets::manifest(Path::new(&data.path), embed.unwrap_or(true), data.span) {
+        Ok(manifest) => manifest,
+        Err(e) => return compile_errors(e),
+    };
+    quote! {
+        {
+            #data;
+            #manifest
+        }
+    }
+    .into()
+}
+
+/// Checks, that a path doesn't exist during compiletime. This is the inverse of [`exists`].
+/// Useful for making sure, that removed or migrated files aren't added back.
+///
+/// The path can also be a glob pattern (e.g. `"**/*.orig"`), which must not match anything.
+/// Patterns are resolved relative to the current directory, like other paths.
+/// A custom explanation can be added to the error with `message = "..."`.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::absent;
+/// #
+/// const _: &str = absent!("config/old.ini", message = "settings were migrated to config/app.toml");
+/// const _: &str = absent!("**/*.orig");
+/// ```
+#[proc_macro]
+pub fn absent(input: TokenStream) -> TokenStream {
+    let data = scout_input!(input as ScoutData<AbsentMatcher>);
+    quote! {
+        #data
+    }
+    .into()
+}
+
+/// Checks the entries of an existing directory during compiletime.
+///
+/// The second argument is either a glob pattern, which the names of the entries are matched with,
+/// or a list of names, which all have to be present. Only direct children are considered.
+///
+/// With a pattern, `min = N` (`1` by default, `0` with `max`) and `max = N` limit the number of matching entries.
+/// With `only`, any entry, which doesn't match the pattern or isn't listed, is an error.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::dir_contains;
+/// #
+/// const MIGRATIONS: &str = dir_contains!("migrations", "*.sql", min = 1);
+/// const LOCALES: &str = dir_contains!("locales", ["en.ftl", "de.ftl"], only);
+/// ```
+#[proc_macro]
+pub fn dir_contains(input: TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        let lit = parse_literal(input)?;
+        input.parse::<Token![,]>()?;
+        let expected = input.parse::<dirs::Expected>()?;
+        let mut bounds = dirs::Bounds::default();
+        let options =
+            ScoutOptions::parse_with(input, |key, input| bounds.parse_option(key, input))?;
+        let data = ScoutData::<ExistsMatcher>::new(&lit, &options)?;
+        if !mode::is_lexical(data.span)? {
+            let dir = Path::new(&data.path);
+            let snapshot = if dir.has_root() {
+                Snapshot::load(data.span)?
+            } else {
+                None
+            };
+            match snapshot {
+                Some(snapshot) => {
+                    dirs::try_contains_recorded(&snapshot, dir, &expected, &bounds, data.span)
+                }
+                None => dirs::try_contains(dir, &expected, &bounds, data.span),
+            }
+            .map_err(|e| options.explain(e))?;
+        }
+        Ok(data)
+    };
+    let data = match parser.parse(input) {
+        Ok(data) => data,
+        Err(e) => return compile_errors(e),
+    };
+    quote! {
+        #data
+    }
+    .into()
+}
+
+/// Checks, that all directories matching a glob pattern contain the same files.
+///
+/// Files are compared by their path relative to each directory, including subdirectories,
+/// but not symlinked ones.
+/// Every directory is checked to contain the files of all others. With `reference = "..."`
+/// one of the matched directories is used as the reference instead, so both missing and
+/// extra files are reported. Each matched directory is scouted the same way as in [`exists`]
+/// and accepts the same options. If feature `"win"` is enabled, all names are checked
+/// for Windows compatibility.
+///
+/// Expands to a slice of the matched directories sorted by path as `&[&str]`.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::consistent_dirs;
+/// #
+/// static LOCALES: &[&str] = consistent_dirs!("locales/*", reference = "locales/en");
+/// ```
+#[proc_macro]
+pub fn consistent_dirs(input: TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        let lit = parse_literal(input)?;
+        let mut reference = None;
+        let options = ScoutOptions::parse_with(input, |key, input| {
+            if key != "reference" {
+                return Ok(false);
+            }
+            if reference.is_some() {
+                return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+            }
+            input.parse::<Token![=]>()?;
+            reference = Some(input.parse::<LitStr>()?);
+            Ok(true)
+        })?;
+
+        let mut dirs = glob::glob(&scouted_path(&lit, &options))
+            .map_err(|e| Error::new(lit.span(), format!("invalid glob pattern: {e}")))?
+            .filter_map(Result::ok)
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>();
+        dirs.sort();
+        if dirs.is_empty() {
+            return Err(options.explain(Error::new(
+                lit.span(),
+                "pattern doesn't match any directory",
+            )));
+        }
+
+        let data = dirs
+            .iter()
+            .map(|dir| {
+                let dir = LitStr::new(&dir.display().to_string(), lit.span());
+                ScoutData::<ExistsMatcher>::new(&dir, &options)
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        let reference =
+            reference.map(|reference| PathBuf::from(scouted_path(&reference, &options)));
+        dirs::try_consistent(&dirs, reference.as_deref(), lit.span())
+            .map_err(|e| options.explain(e))?;
+        Ok(data)
+    };
+    let data = match parser.parse(input) {
+        Ok(data) => data,
+        Err(e) => return compile_errors(e),
+    };
+    quote! {
+        &[#(#data),*]
+    }
+    .into()
+}
+
+/// Lists numbered files in an existing directory, e.g. migrations named `0001_init.sql`.
+///
+/// The required `pattern` describes the file names with the placeholders `{n}` for the number
+/// and optionally `{name}` for the rest of the name. Files not matching the pattern are ignored.
+/// Expands to a slice of `(number, name, path)` as `&[(u64, &str, &str)]` sorted by number.
+///
+/// Duplicate numbers, gaps in the sequence and numbers too large for `u64` fail compilation.
+/// If feature `"win"` is enabled, the file names are checked for Windows compatibility.
+///
+/// The listing isn't tracked, so adding, removing or renaming files doesn't trigger recompilation.
+/// Add `println!("cargo:rerun-if-changed=migrations");` to the build script to rerun it on such changes.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::ordered_files;
+/// #
+/// static MIGRATIONS: &[(u64, &str, &str)] = ordered_files!("migrations", pattern = "{n}_{name}.sql");
+///
+/// for (number, name, path) in MIGRATIONS {
+///     println!("applying {number}: {name}");
+/// }
+/// ```
+#[proc_macro]
+pub fn ordered_files(input: TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        let lit = parse_literal(input)?;
+        let mut pattern = None;
+        let options = ScoutOptions::parse_with(input, |key, input| {
+            if key != "pattern" {
+                return Ok(false);
+            }
+            if pattern.is_some() {
+                return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+            }
+            input.parse::<Token![=]>()?;
+            pattern = Some(sequence::Pattern::new(&input.parse::<LitStr>()?)?);
+            Ok(true)
+        })?;
+        let pattern =
+            pattern.ok_or_else(|| Error::new(lit.span(), "missing option `pattern = \"...\"`"))?;
+        let data = ScoutData::<ExistsMatcher>::new(&lit, &options)?;
+        let files = sequence::ordered_files(Path::new(&data.path), &pattern, data.span)
+            .map_err(|e| options.explain(e))?;
+        Ok((data, files))
+    };
+    let (data, files) = match parser.parse(input) {
+        Ok(parsed) => parsed,
+        Err(e) => return compile_errors(e),
+    };
+    quote! {
+        {
+            #data;
+            #files
+        }
     }
     .into()
 }
@@ -50,13 +688,16 @@ pub fn exists(input: TokenStream) -> TokenStream {
 /// Checks a path for being a valid file at compiletime.
 ///
 /// This does exactly the following list of things:
-/// * checks, if the path is a file path (contains an extension).
+/// * checks, if the path is a file path (contains an extension), unless the kind is given explicitly
+///   with `no_ext` (e.g. `valid_file!("Makefile", no_ext)`) or before the path (e.g. `valid_file!(file "Makefile")`).
 /// * checks, if the path parent tree exists. The file is considered "invalid",
-/// if any parent directory doesn't exist.
+///   if any parent directory doesn't exist.
 /// * checks if the path doesn't lead outside the root (e.g. "/../file.bin").
-/// Even though such path might be handled correctly by some functions, it might break the others.
-/// * if file exists, checks if it's not readonly.
-/// For opening or reading a file, you need to ensure it's existence with [`exists`] instead.
+///   Even though such path might be handled correctly by some functions, it might break the others.
+/// * if file exists, checks if the current user can write to it. Otherwise checks, that it can be created
+///   in its parent directory. Read-only filesystems are detected too.
+///   For opening or reading a file, you need to ensure it's existence with [`exists`] instead.
+/// * if file exists, checks `min_size` and `max_size` limits, if given (see [options](crate#options)).
 /// * if feature `"win"` is enabled, checks the whole path for Windows filesystem compatibility.
 ///
 /// Note, that file doesn't need to exist for this to pass.
@@ -67,7 +708,7 @@ pub fn exists(input: TokenStream) -> TokenStream {
 /// # fn main() -> Result<(), Box<dyn Error>> {
 /// # use fs_scout_macros::valid_file;
 /// #
-/// const IS_VALID: &str = valid_file!("/yes_file.txt");
+/// const IS_VALID: &str = valid_file!("/tmp/yes_file.txt");
 ///
 /// let file = std::fs::File::create(IS_VALID).expect("should be valid");
 /// #   Ok(())
@@ -75,9 +716,9 @@ pub fn exists(input: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn valid_file(input: TokenStream) -> TokenStream {
-    let ScoutData(s, ..) = parse_macro_input!(input as ScoutData<ValidFileMatcher>);
+    let data = scout_input!(input as ScoutData<ValidFileMatcher>);
     quote! {
-        #s
+        #data
     }
     .into()
 }
@@ -85,11 +726,13 @@ pub fn valid_file(input: TokenStream) -> TokenStream {
 /// Checks a path for being a valid file at compiletime.
 ///
 /// This does exactly the following list of things:
-/// * checks, if the path is a directory path (contains no extension).
+/// * checks, if the path is a directory path (contains no extension),
+///   unless it's annotated explicitly (e.g. `valid_dir!(dir "v1.2")`).
 /// * checks, if the path parent tree exists. The directory is considered "invalid",
-/// if any parent directory doesn't exist.
+///   if any parent directory doesn't exist.
 /// * checks if the path doesn't lead outside the root (e.g. "/../dir").
-/// Even though such path might be handled correctly by some functions, it might break the others.
+///   Even though such path might be handled correctly by some functions, it might break the others.
+/// * if the directory doesn't exist, checks if the current user can create it in its parent.
 /// * if feature `"win"` is enabled, checks the whole path for Windows filesystem compatibility.
 ///
 /// Note, that final directory doesn't need to exist for this to pass.
@@ -100,7 +743,7 @@ pub fn valid_file(input: TokenStream) -> TokenStream {
 /// # fn main() -> Result<(), Box<dyn Error>> {
 /// # use fs_scout_macros::valid_dir;
 /// #
-/// const IS_VALID: &str = valid_dir!("/yes_dir");
+/// const IS_VALID: &str = valid_dir!("/tmp/yes_dir");
 ///
 /// let file = std::fs::create_dir_all(IS_VALID).expect("should be valid"); // .create_dir() would panic, if directory exists
 /// #   Ok(())
@@ -108,9 +751,9 @@ pub fn valid_file(input: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn valid_dir(input: TokenStream) -> TokenStream {
-    let ScoutData(s, ..) = parse_macro_input!(input as ScoutData<ValidDirMatcher>);
+    let data = scout_input!(input as ScoutData<ValidDirMatcher>);
     quote! {
-        #s
+        #data
     }
     .into()
 }
@@ -118,9 +761,11 @@ pub fn valid_dir(input: TokenStream) -> TokenStream {
 /// Checks the whole path for being a valid path tree.
 ///
 /// This does exactly the following list of things:
-/// * checks, if the path is a directory path (contains no extension).
+/// * checks, if the path is a directory path (contains no extension),
+///   unless it's annotated explicitly (e.g. `valid_dir_all!(dir "releases/v1.2")`).
 /// * checks if the path doesn't lead outside the root (e.g. "/../dir").
-/// Even though such path might be handled correctly by some functions, it might break the others.
+///   Even though such path might be handled correctly by some functions, it might break the others.
+/// * if the directory doesn't exist, checks if the current user can create entries in its nearest existing ancestor.
 /// * if feature `"win"` is enabled, checks the whole path for Windows filesystem compatibility.
 ///
 /// Note, that no directory in this path needs to exist, for this to pass.
@@ -131,7 +776,7 @@ pub fn valid_dir(input: TokenStream) -> TokenStream {
 /// # fn main() -> Result<(), Box<dyn Error>> {
 /// # use fs_scout_macros::valid_dir_all;
 /// #
-/// const IS_VALID: &str = valid_dir_all!("/yes_1/yes_2/yes_3");
+/// const IS_VALID: &str = valid_dir_all!("/tmp/yes_1/yes_2/yes_3");
 ///
 /// let file = std::fs::create_dir_all(IS_VALID).expect("should be valid");
 /// #   Ok(())
@@ -139,9 +784,102 @@ pub fn valid_dir(input: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn valid_dir_all(input: TokenStream) -> TokenStream {
-    let ScoutData(s, ..) = parse_macro_input!(input as ScoutData<ValidDirAllMatcher>);
+    let data = scout_input!(input as ScoutData<ValidDirAllMatcher>);
+    quote! {
+        #data
+    }
+    .into()
+}
+
+/// Checks many paths like [`valid_file`] at once and expands to them as `[&'static str; N]`.
+///
+/// Every failing path is reported in a single error. Options following the paths apply to all of them.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::valid_files;
+/// #
+/// const OUTPUTS: [&str; 2] = valid_files!["out/report.csv", "out/summary.txt"];
+/// ```
+#[proc_macro]
+pub fn valid_files(input: TokenStream) -> TokenStream {
+    scout_all::<ValidFileMatcher>(input)
+}
+
+/// Checks many paths like [`valid_dir`] at once and expands to them as `[&'static str; N]`.
+///
+/// Every failing path is reported in a single error. Options following the paths apply to all of them.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::valid_dirs;
+/// #
+/// const CACHES: [&str; 2] = valid_dirs!["target/cache", "target/tmp"];
+/// ```
+#[proc_macro]
+pub fn valid_dirs(input: TokenStream) -> TokenStream {
+    scout_all::<ValidDirMatcher>(input)
+}
+
+/// Checks many paths like [`exists`] at once and expands to them as `[&'static str; N]`.
+///
+/// Every failing path is reported in a single error. Options following the paths apply to all of them.
+///
+/// # Examples
+/// ```rust, ignore
+/// # use fs_scout_macros::exists_all;
+/// #
+/// const ICONS: [&str; 2] = exists_all!["assets/icon.png", "assets/icon@2x.png", kind = png];
+/// ```
+#[proc_macro]
+pub fn exists_all(input: TokenStream) -> TokenStream {
+    scout_all::<ExistsMatcher>(input)
+}
+
+/// Parses a list of paths followed by options and scouts each of them, collecting all failures.
+fn scout_all<M: Matcher>(input: TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        let mut paths = vec![parse_literal(input)?];
+        while input.peek(Token![,]) && input.peek2(LitStr) {
+            input.parse::<Token![,]>()?;
+            paths.push(input.parse::<LitStr>()?);
+        }
+        let options = input.parse::<ScoutOptions>()?;
+
+        let mut data = vec![];
+        let mut failures = vec![];
+        for path in &paths {
+            match ScoutData::<M>::new(path, &options) {
+                Ok(scouted) => data.push(scouted),
+                Err(e) => failures.extend(e.into_iter().map(|e| (path.value(), e.to_string()))),
+            }
+        }
+        if failures.is_empty() {
+            return Ok(data);
+        }
+        let failed = failures
+            .iter()
+            .map(|(path, _)| path)
+            .collect::<std::collections::BTreeSet<_>>()
+            .len();
+        Err(Error::new(
+            Span::call_site(),
+            format!(
+                "{failed} of {} paths failed:{}",
+                paths.len(),
+                failures
+                    .iter()
+                    .map(|(path, e)| format!("\n  \"{path}\": {e}"))
+                    .collect::<String>()
+            ),
+        ))
+    };
+    let data = match parser.parse(input) {
+        Ok(data) => data,
+        Err(e) => return compile_errors(e),
+    };
     quote! {
-        #s
+        [#(#data),*]
     }
     .into()
 }
@@ -153,14 +891,139 @@ enum Move {
 }
 
 trait Matcher {
-    fn try_match(path: &Path, span: Span) -> syn::Result<()>;
+    fn try_match(path: &Path, options: &ScoutOptions, span: Span) -> syn::Result<()>;
+
+    /// Checks an absolute path against a snapshot instead of the live disk.
+    fn try_snapshot(
+        path: &Path,
+        options: &ScoutOptions,
+        snapshot: &Snapshot,
+        span: Span,
+    ) -> syn::Result<()>;
+
+    /// Reports dangling symlinks along the path and enforces the symlink policy.
+    fn try_symlinks(path: &Path, options: &ScoutOptions, span: Span) -> syn::Result<()> {
+        symlink::try_symlinks(path, options.symlinks.unwrap_or_default(), span)
+    }
+
+    /// Checks, which don't depend on the filesystem. Used, if the path is only required conditionally
+    /// or `FS_SCOUT_MODE` is `lexical`.
+    fn try_lexical(path: &Path, options: &ScoutOptions, span: Span) -> syn::Result<()> {
+        Self::try_kind(path, options, |_| Lookup::Missing, span)?;
+        Self::try_absolute_components(absolute(path).components(), path, span)
+    }
+
+    /// Checks the path against the kind of item, it has to be, e.g. by its annotation and extension.
+    fn try_kind(
+        _path: &Path,
+        _options: &ScoutOptions,
+        _lookup: impl Fn(&Path) -> Lookup,
+        _span: Span,
+    ) -> syn::Result<()> {
+        Ok(())
+    }
+
+    /// Walks the existing prefix of the path and checks, that every ancestor is a directory.
+    ///
+    /// If `target_is_dir` is given, an existing final component has to be a directory or a non-directory respectively.
+    fn try_existing_prefix(
+        path: &Path,
+        target_is_dir: Option<bool>,
+        lookup: impl Fn(&Path) -> Lookup,
+        span: Span,
+    ) -> syn::Result<()> {
+        let mut prefix = PathBuf::new();
+        let mut components = path.components().peekable();
+        while let Some(component) = components.next() {
+            prefix.push(component);
+            let lookup = lookup(&prefix);
+            if lookup == Lookup::Missing {
+                break;
+            }
+            let is_last = components.peek().is_none();
+            let expects_dir = if is_last { target_is_dir } else { Some(true) };
+            let conflict = match (expects_dir, lookup) {
+                (Some(true), Lookup::File) => "a file",
+                (Some(true), Lookup::Other) => "not a directory",
+                (Some(false), Lookup::Dir) => "a directory",
+                _ => continue,
+            };
+            return Err(Error::new(
+                span,
+                format!(
+                    "{} \"{}\" exists, but is {conflict}",
+                    if is_last { "path" } else { "component" },
+                    prefix.display()
+                ),
+            ));
+        }
+        Ok(())
+    }
 
-    fn try_parent_exists(path: &Path, span: Span) -> syn::Result<()> {
+    /// Checks, that a directory path isn't annotated as another kind or recognized as a file by its extension.
+    fn try_dir_kind(
+        path: &Path,
+        options: &ScoutOptions,
+        lookup: impl Fn(&Path) -> Lookup,
+        span: Span,
+    ) -> syn::Result<()> {
+        if let Some(span) = options.no_ext {
+            return Err(Error::new(
+                span,
+                "`no_ext` only applies to files, use `dir \"...\"` instead",
+            ));
+        }
+        match options.entry {
+            Some((EntryKind::File | EntryKind::Fifo | EntryKind::Socket, span)) => {
+                Err(Error::new(span, "a file can't be used as a directory path"))
+            }
+            None if path.extension().is_some() => {
+                Err(Error::new(span, "this path belongs to a file"))
+            }
+            None => Self::try_existing_prefix(path, Some(true), lookup, span),
+            Some(_) => Self::try_existing_prefix(path, None, lookup, span),
+        }
+    }
+
+    /// Checks, that a file path isn't annotated as a directory and matches the presence of its extension.
+    fn try_file_kind(
+        path: &Path,
+        options: &ScoutOptions,
+        lookup: impl Fn(&Path) -> Lookup,
+        span: Span,
+    ) -> syn::Result<()> {
+        match (options.entry, options.no_ext) {
+            (Some((EntryKind::Dir, span)), _) => {
+                Err(Error::new(span, "a directory can't be used as a file path"))
+            }
+            (_, Some(span)) if path.extension().is_some() => Err(Error::new(
+                span,
+                format!(
+                    "`no_ext` is given, but \"{}\" has an extension",
+                    path.display()
+                ),
+            )),
+            // Without an explicit kind, a file is recognized by its extension.
+            (None, None) if path.extension().is_none() => {
+                Err(Error::new(span, "this path belongs to a directory"))
+            }
+            (entry, _) => {
+                let target_is_dir = entry.is_none().then_some(false);
+                Self::try_existing_prefix(path, target_is_dir, lookup, span)
+            }
+        }
+    }
+
+    fn try_parent_exists(
+        path: &Path,
+        lookup: impl Fn(&Path) -> Lookup,
+        span: Span,
+    ) -> syn::Result<()> {
         match path.parent() {
             None => Ok(()),
-            Some(parent) => match parent.try_exists() {
-                Ok(true) => Ok(()),
-                _ => Err(Error::new(
+            Some(parent) => match lookup(parent) {
+                Lookup::Dir | Lookup::File | Lookup::Other => Ok(()),
+                Lookup::Missing => Err(Error::new(
                     span,
                     format!("parent path \"{}\" doesn't exist", parent.display()),
                 )),
@@ -169,7 +1032,7 @@ trait Matcher {
     }
 
     fn try_absolute(path: &Path, span: Span) -> syn::Result<()> {
-        match path.components().last() {
+        match path.components().next_back() {
             None => return Err(Error::new(span, "empty path")),
             Some(last) => match last {
                 Component::Normal(name) => {
@@ -240,172 +1103,611 @@ trait Matcher {
     }
 }
 
-struct ScoutData<M: Matcher + ?Sized>(String, PhantomData<M>);
+struct ScoutData<M: Matcher + ?Sized> {
+    /// The scouted path, which the filesystem is accessed with.
+    path: String,
+    /// The path written into the expansion, which may be normalized or use other separators.
+    expansion: String,
+    span: Span,
+    /// Files, the expansion depends on the contents of. Changing these triggers recompilation.
+    tracked: Vec<PathBuf>,
+    lints: Vec<Lint>,
+    _matcher: PhantomData<M>,
+}
+
+impl<M: Matcher> ScoutData<M> {
+    fn absolute(&self) -> String {
+        absolute(Path::new(&self.path)).display().to_string()
+    }
+
+    /// Requires the path to be a file and makes the expansion depend on its contents.
+    fn track_file(&mut self) -> syn::Result<std::fs::Metadata> {
+        let path = Path::new(&self.path);
+        let metadata = path
+            .metadata()
+            .map_err(|e| Error::new(self.span, format!("can't access \"{}\": {e}", self.path)))?;
+        if !metadata.is_file() {
+            return Err(Error::new(
+                self.span,
+                format!("\"{}\" is not a file", self.path),
+            ));
+        }
+        let absolute = absolute(path);
+        if !self.tracked.contains(&absolute) {
+            self.tracked.push(absolute);
+        }
+        Ok(metadata)
+    }
+
+    fn read(&self) -> syn::Result<Vec<u8>> {
+        std::fs::read(&self.path)
+            .map_err(|e| Error::new(self.span, format!("can't read \"{}\": {e}", self.path)))
+    }
+
+    fn try_contents(
+        path: &Path,
+        contents: &[u8],
+        options: &ScoutOptions,
+        span: Span,
+    ) -> syn::Result<()> {
+        let mut results = options
+            .pins
+            .iter()
+            .map(|pin| pin.verify(&path.display().to_string(), contents))
+            .collect::<Vec<_>>();
+        if let Some((kind, span)) = options.kind {
+            results.push(magic::try_kind(path, kind, contents, span));
+        }
+        if let Some(format) = options.format {
+            results.push(format::try_format(path, format, contents, span));
+        }
+        combine_errors(results.into_iter().filter_map(Result::err))
+    }
+
+    /// Reads only as many bytes, as are needed to detect the kind of a file.
+    fn read_header(path: &Path) -> std::io::Result<Vec<u8>> {
+        let mut header = Vec::with_capacity(magic::HEADER_LEN);
+        std::fs::File::open(path)?
+            .take(magic::HEADER_LEN as u64)
+            .read_to_end(&mut header)?;
+        Ok(header)
+    }
+}
 
 impl<M: Matcher> Parse for ScoutData<M> {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let lookahead = input.lookahead1();
-        if lookahead.peek(LitStr) {
-            let lit = input.parse::<LitStr>()?;
-            let path_string = lit.value();
-            let path = Path::new(&path_string);
-            let span = lit.span();
+        Self::parse_with(input, |_, _| Ok(false))
+    }
+}
 
-            M::try_match(path, span)?;
+impl<M: Matcher> ScoutData<M> {
+    /// Runs the matcher and all checks requested by options.
+    ///
+    /// Returns the files the expansion depends on and the warnings to emit.
+    fn scout(
+        path: &Path,
+        options: &ScoutOptions,
+        span: Span,
+    ) -> syn::Result<(Vec<PathBuf>, Vec<Lint>)> {
+        Self::scout_in(mode::mode(span)?, path, options, span)
+    }
 
-            Ok(Self(path_string, PhantomData))
+    /// Like [`Self::scout`], but with an explicit mode instead of the one set by `FS_SCOUT_MODE`.
+    fn scout_in(
+        mode: Mode,
+        path: &Path,
+        options: &ScoutOptions,
+        span: Span,
+    ) -> syn::Result<(Vec<PathBuf>, Vec<Lint>)> {
+        if mode == Mode::Lexical {
+            root::try_confined_lexically(path, span)?;
+            M::try_lexical(path, options, span)?;
+            return Ok((vec![], mode::note(span).into_iter().collect()));
+        }
+        // Snapshot paths like `/etc/app.toml` aren't absolute on Windows hosts, but name the deployment anyway.
+        let snapshot = if path.has_root() {
+            Snapshot::load(span)?
         } else {
-            Err(Error::new(
-                input.span(),
+            None
+        };
+        if snapshot.is_none() {
+            root::try_confined(path, span)?;
+        }
+        if let Some(when) = &options.when {
+            if !when.eval()? {
+                M::try_lexical(path, options, span)?;
+                return Ok((vec![], vec![]));
+            }
+        }
+        if let Some(snapshot) = snapshot {
+            return Self::scout_snapshot(path, options, &snapshot, mode == Mode::Diff, span);
+        }
+        M::try_symlinks(path, options, span)?;
+        M::try_match(path, options, span)?;
+        if let Some((kind, span)) = options.entry {
+            entry::try_entry(path, kind, span)?;
+        }
+        for &(access, span) in &options.access {
+            access::try_access(path, access, span)?;
+        }
+
+        let mut tracked = vec![];
+        let mut lints = vec![];
+        if options.reads_contents() {
+            let contents = std::fs::read(path).map_err(|e| {
+                Error::new(
+                    span,
+                    format!(
+                        "can't read \"{}\" to check its contents: {e}",
+                        path.display()
+                    ),
+                )
+            })?;
+            Self::try_contents(path, &contents, options, span)?;
+            lints.extend(magic::lint_extension(path, &contents).map(|l| Lint::new(l, span)));
+            tracked.push(absolute(path));
+        } else if path.is_file() {
+            if let Ok(header) = Self::read_header(path) {
+                lints.extend(magic::lint_extension(path, &header).map(|l| Lint::new(l, span)));
+            }
+        }
+        // Size limits only apply to items, which already exist (e.g. for `valid_file`).
+        if options.constrains_size() && matches!(path.try_exists(), Ok(true)) {
+            size::try_size(
+                path,
+                options.min_size.as_ref(),
+                options.max_size.as_ref(),
+                span,
+            )?;
+        }
+
+        Ok((tracked, lints))
+    }
+
+    /// Runs the matcher against a snapshot. Checks, which need the real item, are skipped with a warning.
+    fn scout_snapshot(
+        path: &Path,
+        options: &ScoutOptions,
+        snapshot: &Snapshot,
+        diff: bool,
+        span: Span,
+    ) -> syn::Result<(Vec<PathBuf>, Vec<Lint>)> {
+        M::try_snapshot(path, options, snapshot, span)?;
+        if let Some((kind, span)) = options.entry {
+            snapshot.try_entry(path, kind, span)?;
+        }
+
+        let mut lints = vec![];
+        if options.reads_contents()
+            || options.constrains_size()
+            || !options.access.is_empty()
+            || options.symlinks.is_some()
+        {
+            lints.push(Lint::new(
                 format!(
-                    "invalid input, expected string literal; input: {}",
-                    input.to_string()
+                    "\"{}\" is checked against the snapshot, \
+                     so its contents, size, access rights and symlinks were not verified",
+                    path.display()
                 ),
-            ))
+                span,
+            ));
+        }
+        if diff {
+            lints.extend(snapshot.lint_diff(path, span));
+        }
+        Ok((vec![snapshot.file.clone()], lints))
+    }
+
+    /// Parses the path and its options. See [`ScoutOptions::parse_with`] for `extra`.
+    fn parse_with(
+        input: syn::parse::ParseStream,
+        extra: impl FnMut(&Ident, syn::parse::ParseStream) -> syn::Result<bool>,
+    ) -> syn::Result<Self> {
+        let entry = if input.peek(Ident) {
+            let kind = input.parse::<Ident>()?;
+            Some((EntryKind::from_ident(&kind)?, kind.span()))
+        } else {
+            None
+        };
+        let lit = parse_literal(input)?;
+        let mut options = ScoutOptions::parse_with(input, extra)?;
+        options.entry = entry;
+        Self::new(&lit, &options)
+    }
+
+    /// Scouts an already parsed path with its options.
+    fn new(lit: &LitStr, options: &ScoutOptions) -> syn::Result<Self> {
+        let span = lit.span();
+        let profiles = platform::Profiles::load(span)?;
+        let path_string = scouted_path(lit, options);
+        if options.sep.is_none() && profiles.unix {
+            separator::try_backslashes(&path_string, span).map_err(|e| options.explain(e))?;
+        }
+        platform::try_portable(&path_string, &profiles, span).map_err(|e| options.explain(e))?;
+        let path = Path::new(&path_string);
+
+        let (tracked, mut lints) =
+            Self::scout(path, options, span).map_err(|e| options.explain(e))?;
+        lints.extend(platform::lint_anchor(&path_string, &profiles, span));
+        if options.sep.is_none() {
+            lints.extend(separator::lint_mixed(&path_string, span));
+        }
+        let mut expansion = path_string.clone();
+        if options.normalize {
+            let (normalized, lint) = normalize::normalize_path(&expansion, span);
+            expansion = normalized;
+            lints.extend(lint);
+        }
+        if let Some(sep) = options.sep {
+            expansion = separator::rewrite(&expansion, sep);
+        }
+
+        Ok(Self {
+            path: path_string,
+            expansion,
+            span,
+            tracked,
+            lints,
+            _matcher: PhantomData,
+        })
+    }
+}
+
+/// The path accessed on the filesystem for a literal.
+///
+/// With an explicit separator, both `/` and `\` are separators and the native one is scouted.
+fn scouted_path(lit: &LitStr, options: &ScoutOptions) -> String {
+    match options.sep {
+        Some(_) => separator::rewrite(&lit.value(), separator::Separator::Native),
+        None => lit.value(),
+    }
+}
+
+/// Checks, if a path exists, in the snapshot for absolute paths, if one is set.
+fn path_exists(path: &Path, span: Span) -> syn::Result<bool> {
+    let snapshot = if snapshot::applies(path) {
+        Snapshot::load(span)?
+    } else {
+        None
+    };
+    Ok(snapshot::exists(snapshot.as_deref(), path))
+}
+
+fn parse_literal(input: syn::parse::ParseStream) -> syn::Result<LitStr> {
+    let lookahead = input.lookahead1();
+    if lookahead.peek(LitStr) {
+        input.parse::<LitStr>()
+    } else {
+        Err(Error::new(
+            input.span(),
+            format!("invalid input, expected string literal; input: {input}"),
+        ))
+    }
+}
+
+impl<M: Matcher> ToTokens for ScoutData<M> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let path = &self.expansion;
+        let env = tracked_env();
+        let tracked = self.tracked.iter().map(|p| p.display().to_string());
+        let lints = &self.lints;
+        tokens.extend(quote! {
+            {
+                #env
+                #(const _: &[u8] = ::core::include_bytes!(#tracked);)*
+                #(#lints)*
+                #path
+            }
+        });
+    }
+}
+
+/// Makes the expansion depend on the environment variables, which the checks read.
+///
+/// Cargo only tracks variables set by a build script, so without this, changing one in the shell
+/// (e.g. `FS_SCOUT_MODE=lexical cargo build`) would reuse artifacts checked with the old value.
+fn tracked_env() -> proc_macro2::TokenStream {
+    let vars = [
+        mode::MODE_VAR,
+        snapshot::SNAPSHOT_VAR,
+        root::ROOT_VAR,
+        condition::FEATURES_VAR,
+        platform::PROFILES_VAR,
+    ];
+    quote! {
+        #(const _: ::core::option::Option<&str> = ::core::option_env!(#vars);)*
+    }
+}
+
+/// Expands to all errors at once.
+///
+/// Unlike [`Error::into_compile_error`], the errors are wrapped in a block, so multiple of them
+/// are still valid in expression position.
+fn compile_errors(errors: Error) -> TokenStream {
+    let errors = errors.into_iter().map(|e| e.into_compile_error());
+    quote! {
+        {
+            #(#errors)*
         }
     }
+    .into()
+}
+
+/// Reports all errors at once. Succeeds, if there are none.
+fn combine_errors(errors: impl IntoIterator<Item = Error>) -> syn::Result<()> {
+    match errors.into_iter().reduce(|mut errors, e| {
+        errors.combine(e);
+        errors
+    }) {
+        None => Ok(()),
+        Some(errors) => Err(errors),
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    cache::current_dir().join(path)
 }
 
 struct ExistsMatcher;
 
 impl Matcher for ExistsMatcher {
-    fn try_match(path: &Path, span: Span) -> syn::Result<()> {
+    fn try_match(path: &Path, _options: &ScoutOptions, span: Span) -> syn::Result<()> {
         if let Ok(true) = path.try_exists() {
             Ok(())
         } else {
+            Self::try_existing_prefix(path, None, cache::lookup, span)?;
             Err(Error::new(span, "path doesn't exist"))
         }
     }
+
+    fn try_snapshot(
+        path: &Path,
+        _options: &ScoutOptions,
+        snapshot: &Snapshot,
+        span: Span,
+    ) -> syn::Result<()> {
+        if snapshot.lookup(path) == Lookup::Missing {
+            Self::try_existing_prefix(path, None, |prefix| snapshot.lookup(prefix), span)?;
+            return Err(Error::new(span, "path doesn't exist in the snapshot"));
+        }
+        Ok(())
+    }
 }
-struct ValidFileMatcher;
+struct AbsentMatcher;
 
-impl Matcher for ValidFileMatcher {
-    fn try_match(path: &Path, span: Span) -> syn::Result<()> {
-        match path.extension() {
-            None => return Err(Error::new(span, "this path belongs to a directory")),
-            Some(_) => {
-                if let Ok(true) = path.try_exists() {
-                    if path
-                        .metadata()
-                        .expect("should have metadata permissions")
-                        .permissions()
-                        .readonly()
-                    {
-                        return Err(Error::new(span, "file is readonly"));
-                    }
-                }
+impl Matcher for AbsentMatcher {
+    /// Glob patterns aren't valid names, so there is nothing to check.
+    fn try_lexical(_path: &Path, _options: &ScoutOptions, _span: Span) -> syn::Result<()> {
+        Ok(())
+    }
+
+    /// Any symlink, even a dangling one, is an existing entry.
+    fn try_symlinks(_path: &Path, _options: &ScoutOptions, _span: Span) -> syn::Result<()> {
+        Ok(())
+    }
+
+    fn try_match(path: &Path, _options: &ScoutOptions, span: Span) -> syn::Result<()> {
+        let pattern = path.to_str().expect("path should be valid UTF-8");
+        let is_verbatim = pattern.starts_with(r"\\?\");
+        if !is_verbatim && pattern.contains(['*', '?', '[']) {
+            let matches = glob::glob(pattern)
+                .map_err(|e| Error::new(span, format!("invalid glob pattern: {e}")))?
+                .filter_map(Result::ok)
+                .collect::<Vec<_>>();
+            match matches.as_slice() {
+                [] => Ok(()),
+                [first, rest @ ..] => Err(Error::new(
+                    span,
+                    format!(
+                        "pattern \"{pattern}\" matches \"{}\"{}",
+                        first.display(),
+                        match rest.len() {
+                            0 => String::new(),
+                            n => format!(" and {n} more"),
+                        }
+                    ),
+                )),
             }
+        } else if path.symlink_metadata().is_ok() {
+            match (path.metadata(), path.read_link()) {
+                (Err(_), Ok(target)) => Err(Error::new(
+                    span,
+                    format!(
+                        "path exists as a dangling symlink to \"{}\"",
+                        target.display()
+                    ),
+                )),
+                _ => Err(Error::new(span, "path exists")),
+            }
+        } else {
+            Ok(())
         }
+    }
+
+    fn try_snapshot(
+        path: &Path,
+        _options: &ScoutOptions,
+        snapshot: &Snapshot,
+        span: Span,
+    ) -> syn::Result<()> {
+        let pattern = path.to_str().expect("path should be valid UTF-8");
+        if !pattern.starts_with(r"\\?\") && pattern.contains(['*', '?', '[']) {
+            match snapshot.matching(pattern, span)?.as_slice() {
+                [] => Ok(()),
+                [first, rest @ ..] => Err(Error::new(
+                    span,
+                    format!(
+                        "pattern \"{pattern}\" matches \"{}\"{} in the snapshot",
+                        first.display(),
+                        match rest.len() {
+                            0 => String::new(),
+                            n => format!(" and {n} more"),
+                        }
+                    ),
+                )),
+            }
+        } else if snapshot.lookup(path) == Lookup::Missing {
+            Ok(())
+        } else {
+            Err(Error::new(span, "path exists in the snapshot"))
+        }
+    }
+}
+
+struct ValidFileMatcher;
+
+impl Matcher for ValidFileMatcher {
+    fn try_kind(
+        path: &Path,
+        options: &ScoutOptions,
+        lookup: impl Fn(&Path) -> Lookup,
+        span: Span,
+    ) -> syn::Result<()> {
+        Self::try_file_kind(path, options, lookup, span)
+    }
+
+    fn try_match(path: &Path, options: &ScoutOptions, span: Span) -> syn::Result<()> {
+        Self::try_kind(path, options, cache::lookup, span)?;
 
         if path.is_absolute() {
-            Self::try_parent_exists(path, span)?;
-            Self::try_absolute(path, span)
+            Self::try_parent_exists(path, cache::lookup, span)?;
+            Self::try_absolute(path, span)?;
         } else {
-            let abs_current_dir = std::env::current_dir().expect("current dir should be valid");
-            abs_current_dir.metadata().map_err(|_| {
-                Error::new(
+            let abs_current_dir = cache::current_dir();
+            if cache::lookup(&abs_current_dir) == Lookup::Missing {
+                return Err(Error::new(
                     span,
                     format!(
                         "lacks permission to access parent at \"{}\"",
                         abs_current_dir.display()
                     ),
-                )
-            })?;
+                ));
+            }
             let abs_path = abs_current_dir.join(path);
-            Self::try_parent_exists(&abs_path, span)?;
-            Self::try_absolute(&abs_path, span)
+            Self::try_parent_exists(&abs_path, cache::lookup, span)?;
+            Self::try_absolute(&abs_path, span)?;
         }
+        // An existing file is overwritten, otherwise it's created in its parent.
+        access::try_access(path, Access::Write, span)
+    }
+
+    /// Only the layout is recorded, so the access rights aren't checked.
+    fn try_snapshot(
+        path: &Path,
+        options: &ScoutOptions,
+        snapshot: &Snapshot,
+        span: Span,
+    ) -> syn::Result<()> {
+        let lookup = |prefix: &Path| snapshot.lookup(prefix);
+        Self::try_kind(path, options, lookup, span)?;
+        Self::try_parent_exists(path, lookup, span)?;
+        Self::try_absolute(path, span)
     }
 }
 
 struct ValidDirMatcher;
 
 impl Matcher for ValidDirMatcher {
-    fn try_match(path: &Path, span: Span) -> syn::Result<()> {
-        if let Some(_) = path.extension() {
-            return Err(Error::new(span, "this path belongs to a file"));
-        }
+    fn try_kind(
+        path: &Path,
+        options: &ScoutOptions,
+        lookup: impl Fn(&Path) -> Lookup,
+        span: Span,
+    ) -> syn::Result<()> {
+        Self::try_dir_kind(path, options, lookup, span)
+    }
+
+    fn try_match(path: &Path, options: &ScoutOptions, span: Span) -> syn::Result<()> {
+        Self::try_kind(path, options, cache::lookup, span)?;
 
         if path.is_absolute() {
-            Self::try_parent_exists(path, span)?;
-            Self::try_absolute(path, span)
+            Self::try_parent_exists(path, cache::lookup, span)?;
+            Self::try_absolute(path, span)?;
         } else {
-            let abs_current_dir = std::env::current_dir().expect("current dir should be valid");
-            abs_current_dir.metadata().map_err(|_| {
-                Error::new(
+            let abs_current_dir = cache::current_dir();
+            if cache::lookup(&abs_current_dir) == Lookup::Missing {
+                return Err(Error::new(
                     span,
                     format!(
                         "lacks permission to access parent at \"{}\"",
                         abs_current_dir.display()
                     ),
-                )
-            })?;
+                ));
+            }
             let abs_path = abs_current_dir.join(path);
-            Self::try_parent_exists(&abs_path, span)?;
-            Self::try_absolute(&abs_path, span)
+            Self::try_parent_exists(&abs_path, cache::lookup, span)?;
+            Self::try_absolute(&abs_path, span)?;
         }
+        match path.try_exists() {
+            Ok(true) => Ok(()),
+            _ => access::try_creatable(path, span),
+        }
+    }
+
+    /// Only the layout is recorded, so the access rights aren't checked.
+    fn try_snapshot(
+        path: &Path,
+        options: &ScoutOptions,
+        snapshot: &Snapshot,
+        span: Span,
+    ) -> syn::Result<()> {
+        let lookup = |prefix: &Path| snapshot.lookup(prefix);
+        Self::try_kind(path, options, lookup, span)?;
+        Self::try_parent_exists(path, lookup, span)?;
+        Self::try_absolute(path, span)
     }
 }
 
 struct ValidDirAllMatcher;
 
 impl Matcher for ValidDirAllMatcher {
-    fn try_match(path: &Path, span: Span) -> syn::Result<()> {
-        if let Some(_) = path.extension() {
-            return Err(Error::new(span, "this path belongs to a file"));
-        }
+    fn try_kind(
+        path: &Path,
+        options: &ScoutOptions,
+        lookup: impl Fn(&Path) -> Lookup,
+        span: Span,
+    ) -> syn::Result<()> {
+        Self::try_dir_kind(path, options, lookup, span)
+    }
+
+    fn try_match(path: &Path, options: &ScoutOptions, span: Span) -> syn::Result<()> {
+        Self::try_kind(path, options, cache::lookup, span)?;
 
         if path.is_absolute() {
-            Self::try_absolute(path, span)
+            Self::try_absolute(path, span)?;
         } else {
-            let abs_current_dir = std::env::current_dir().expect("current dir should be valid");
-            abs_current_dir.metadata().map_err(|_| {
-                Error::new(
+            let abs_current_dir = cache::current_dir();
+            if cache::lookup(&abs_current_dir) == Lookup::Missing {
+                return Err(Error::new(
                     span,
                     format!(
                         "lacks permission to access parent at \"{}\"",
                         abs_current_dir.display()
                     ),
-                )
-            })?;
+                ));
+            }
             let abs_path = abs_current_dir.join(path);
-            Self::try_absolute(&abs_path, span)
+            Self::try_absolute(&abs_path, span)?;
+        }
+        match path.try_exists() {
+            Ok(true) => Ok(()),
+            _ => access::try_creatable(path, span),
         }
     }
+
+    /// Only the layout is recorded, so the access rights aren't checked.
+    fn try_snapshot(
+        path: &Path,
+        options: &ScoutOptions,
+        snapshot: &Snapshot,
+        span: Span,
+    ) -> syn::Result<()> {
+        Self::try_kind(path, options, |prefix| snapshot.lookup(prefix), span)?;
+        Self::try_absolute(path, span)
+    }
 }
 
 #[cfg(feature = "win")]
 fn check_name(name: &str, span: Span) -> syn::Result<()> {
-    let invalid_trailing = regex::Regex::new(r".*[. ]$").unwrap();
-    if invalid_trailing.is_match(name) {
-        Err(Error::new(
-            span,
-            format!(
-                "invalid character in trailing position: '{}'",
-                invalid_trailing
-                    .find(name)
-                    .unwrap()
-                    .as_str()
-                    .chars()
-                    .last()
-                    .expect("should be non-empty")
-            ),
-        ))
-    } else {
-        let invalid_sequences = regex::Regex::new(
-            r#"[<>:"/\\|?*\x00-\x1F]|^(?i:CON|PRN|AUX|NUL|COM[0-9]|LPT[0-9])(?:\..+)?$"#,
-        )
-        .unwrap();
-        if invalid_sequences.is_match(name) {
-            Err(Error::new(
-                span,
-                format!(
-                    "invalid character sequence: \"{}\"",
-                    invalid_sequences.find(name).unwrap().as_str()
-                ),
-            ))
-        } else {
-            Ok(())
-        }
-    }
+    win::validate(name).map_err(|e| Error::new(span, e.to_string()))
 }
@@ -0,0 +1,38 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote_spanned, ToTokens};
+
+/// A warning attached to the expansion.
+///
+/// Stable proc macros can't emit warnings directly, so this expands to a use of a deprecated item,
+/// which makes the compiler report the message. It can be silenced with `#[allow(deprecated)]`.
+pub(crate) struct Lint {
+    message: String,
+    span: Span,
+}
+
+impl Lint {
+    pub(crate) fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl ToTokens for Lint {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let note = format!("fs-scout: {}", self.message);
+        tokens.extend(quote_spanned! {self.span=>
+            {
+                #[deprecated(note = #note)]
+                struct Lint;
+                let _ = Lint;
+            }
+        });
+    }
+}
@@ -0,0 +1,198 @@
+use proc_macro2::Span;
+use std::path::Path;
+use syn::{Error, Ident};
+
+/// Number of leading bytes needed to detect any binary [`FileKind`].
+pub(crate) const HEADER_LEN: usize = 512;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FileKind {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Bmp,
+    Gzip,
+    Bzip2,
+    Xz,
+    Zstd,
+    Zip,
+    SevenZip,
+    Tar,
+    Elf,
+    Pe,
+    Wasm,
+    Pdf,
+    Sqlite,
+    Utf8,
+}
+
+impl FileKind {
+    const ALL: [FileKind; 18] = [
+        Self::Png,
+        Self::Jpeg,
+        Self::Gif,
+        Self::Webp,
+        Self::Bmp,
+        Self::Gzip,
+        Self::Bzip2,
+        Self::Xz,
+        Self::Zstd,
+        Self::Zip,
+        Self::SevenZip,
+        Self::Tar,
+        Self::Elf,
+        Self::Pe,
+        Self::Wasm,
+        Self::Pdf,
+        Self::Sqlite,
+        Self::Utf8,
+    ];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpeg",
+            Self::Gif => "gif",
+            Self::Webp => "webp",
+            Self::Bmp => "bmp",
+            Self::Gzip => "gzip",
+            Self::Bzip2 => "bzip2",
+            Self::Xz => "xz",
+            Self::Zstd => "zstd",
+            Self::Zip => "zip",
+            Self::SevenZip => "seven_zip",
+            Self::Tar => "tar",
+            Self::Elf => "elf",
+            Self::Pe => "pe",
+            Self::Wasm => "wasm",
+            Self::Pdf => "pdf",
+            Self::Sqlite => "sqlite",
+            Self::Utf8 => "utf8",
+        }
+    }
+
+    /// Extensions, which imply this kind. Used to lint files, whose contents don't match their name.
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Png => &["png"],
+            Self::Jpeg => &["jpg", "jpeg"],
+            Self::Gif => &["gif"],
+            Self::Webp => &["webp"],
+            Self::Bmp => &["bmp"],
+            Self::Gzip => &["gz", "tgz"],
+            Self::Bzip2 => &["bz2"],
+            Self::Xz => &["xz"],
+            Self::Zstd => &["zst"],
+            Self::Zip => &["zip", "jar", "docx", "xlsx", "pptx", "apk"],
+            Self::SevenZip => &["7z"],
+            Self::Tar => &["tar"],
+            Self::Pe => &["exe", "dll"],
+            Self::Wasm => &["wasm"],
+            Self::Pdf => &["pdf"],
+            Self::Sqlite => &["sqlite", "sqlite3"],
+            Self::Elf | Self::Utf8 => &[],
+        }
+    }
+
+    pub(crate) fn from_ident(ident: &Ident) -> syn::Result<Self> {
+        let name = ident.to_string();
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+            .ok_or_else(|| {
+                Error::new(
+                    ident.span(),
+                    format!(
+                        "unknown file kind `{name}`, expected one of: {}",
+                        Self::ALL.map(Self::name).join(", ")
+                    ),
+                )
+            })
+    }
+
+    fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.extensions().contains(&extension.as_str()))
+    }
+
+    /// Checks the leading bytes of a file. [`FileKind::Utf8`] needs the whole file instead.
+    fn matches(self, bytes: &[u8]) -> bool {
+        match self {
+            Self::Png => bytes.starts_with(b"\x89PNG\r\n\x1a\n"),
+            Self::Jpeg => bytes.starts_with(b"\xff\xd8\xff"),
+            Self::Gif => bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a"),
+            Self::Webp => bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP"),
+            Self::Bmp => bytes.starts_with(b"BM"),
+            Self::Gzip => bytes.starts_with(b"\x1f\x8b"),
+            Self::Bzip2 => bytes.starts_with(b"BZh"),
+            Self::Xz => bytes.starts_with(b"\xfd7zXZ\x00"),
+            Self::Zstd => bytes.starts_with(b"\x28\xb5\x2f\xfd"),
+            Self::Zip => {
+                bytes.starts_with(b"PK\x03\x04")
+                    || bytes.starts_with(b"PK\x05\x06")
+                    || bytes.starts_with(b"PK\x07\x08")
+            }
+            Self::SevenZip => bytes.starts_with(b"7z\xbc\xaf\x27\x1c"),
+            Self::Tar => bytes.get(257..262) == Some(b"ustar"),
+            Self::Elf => bytes.starts_with(b"\x7fELF"),
+            Self::Pe => bytes.starts_with(b"MZ"),
+            Self::Wasm => bytes.starts_with(b"\0asm"),
+            Self::Pdf => bytes.starts_with(b"%PDF-"),
+            Self::Sqlite => bytes.starts_with(b"SQLite format 3\0"),
+            Self::Utf8 => std::str::from_utf8(bytes).is_ok(),
+        }
+    }
+
+    /// Detects the binary kind of a file from its leading bytes.
+    fn detect(header: &[u8]) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|&kind| kind != Self::Utf8)
+            .find(|kind| kind.matches(header))
+    }
+}
+
+pub(crate) fn try_kind(
+    path: &Path,
+    kind: FileKind,
+    contents: &[u8],
+    span: Span,
+) -> syn::Result<()> {
+    if kind.matches(contents) {
+        return Ok(());
+    }
+    let detected = match FileKind::detect(contents) {
+        Some(detected) => format!(", but looks like {}", detected.name()),
+        None => String::new(),
+    };
+    Err(Error::new(
+        span,
+        format!(
+            "\"{}\" is not a valid {} file{detected}",
+            path.display(),
+            kind.name()
+        ),
+    ))
+}
+
+/// Compares the leading bytes of a file with the kind implied by its extension.
+///
+/// Returns a warning message on a mismatch. Empty files and unknown extensions are not reported.
+pub(crate) fn lint_extension(path: &Path, header: &[u8]) -> Option<String> {
+    let expected = FileKind::from_extension(path)?;
+    if header.is_empty() || expected.matches(header) {
+        return None;
+    }
+    let detected = match FileKind::detect(header) {
+        Some(detected) => format!("looks like {}", detected.name()),
+        None => "doesn't look like it".to_string(),
+    };
+    Some(format!(
+        "\"{}\" has a {} extension, but {detected}",
+        path.display(),
+        expected.name()
+    ))
+}
@@ -0,0 +1,117 @@
+use proc_macro2::Span;
+use syn::Error;
+
+use crate::{lint::Lint, snapshot::SNAPSHOT_VAR};
+
+/// Environment variable selecting, whether and how the filesystem is consulted.
+/// Set to `lexical` for builds without the real files, e.g. on docs.rs or in sandboxes.
+pub(crate) const MODE_VAR: &str = "FS_SCOUT_MODE";
+
+/// How paths are checked, chosen with [`MODE_VAR`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Mode {
+    /// Against the live disk, or the snapshot for absolute paths, if one is set.
+    Full,
+    /// Only the checks not touching the filesystem.
+    Lexical,
+    /// Absolute paths against the snapshot, warning, where the live disk disagrees with it.
+    Diff,
+}
+
+pub(crate) fn mode(span: Span) -> syn::Result<Mode> {
+    match std::env::var(MODE_VAR).as_deref() {
+        Err(_) | Ok("" | "full") => Ok(Mode::Full),
+        Ok("lexical") => Ok(Mode::Lexical),
+        Ok("diff") if std::env::var_os(SNAPSHOT_VAR).is_some() => Ok(Mode::Diff),
+        Ok("diff") => Err(Error::new(
+            span,
+            format!("{MODE_VAR} `diff` requires a snapshot to be set with {SNAPSHOT_VAR}"),
+        )),
+        Ok(other) => Err(Error::new(
+            span,
+            format!("unknown {MODE_VAR} `{other}`, expected one of: full, lexical, diff"),
+        )),
+    }
+}
+
+/// Checks, if only the checks not touching the filesystem should run.
+pub(crate) fn is_lexical(span: Span) -> syn::Result<bool> {
+    Ok(mode(span)? == Mode::Lexical)
+}
+
+/// Notes once per compilation, that paths were only checked lexically.
+pub(crate) fn note(span: Span) -> Option<Lint> {
+    crate::cache::first_lexical_note().then(|| {
+        Lint::new(
+            format!("{MODE_VAR} is `lexical`, so the existence of paths was not verified"),
+            span,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mode;
+    use crate::{
+        options::ScoutOptions, ExistsMatcher, Matcher, ScoutData, ValidDirMatcher, ValidFileMatcher,
+    };
+    use proc_macro2::Span;
+    use std::path::Path;
+
+    fn lexical<M: Matcher>(path: &str, options: &str) -> Result<(), String> {
+        let options = syn::parse_str::<ScoutOptions>(options).unwrap();
+        ScoutData::<M>::scout_in(Mode::Lexical, Path::new(path), &options, Span::call_site())
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn skips_filesystem() {
+        assert_eq!(lexical::<ExistsMatcher>("missing/file.bin", ""), Ok(()));
+        assert_eq!(
+            lexical::<ExistsMatcher>(
+                "missing/file.bin",
+                ", sha256 = \"f7edcdf2037ecd4ae250d46ee57cb03573eea8d88657bccef3c5631a9eb03935\", max_size = 1"
+            ),
+            Ok(())
+        );
+        assert_eq!(lexical::<ValidFileMatcher>("missing/file.bin", ""), Ok(()));
+        assert_eq!(lexical::<ValidDirMatcher>("missing/dir", ""), Ok(()));
+    }
+
+    #[test]
+    fn keeps_kinds() {
+        assert_eq!(
+            lexical::<ValidFileMatcher>("data/noext", ""),
+            Err("this path belongs to a directory".to_string())
+        );
+        assert_eq!(
+            lexical::<ValidFileMatcher>("data/a.txt", ", no_ext"),
+            Err("`no_ext` is given, but \"data/a.txt\" has an extension".to_string())
+        );
+        assert_eq!(
+            lexical::<ValidDirMatcher>("data/a.txt", ""),
+            Err("this path belongs to a file".to_string())
+        );
+        assert_eq!(
+            lexical::<ValidDirMatcher>("data/a.txt", ", no_ext"),
+            Err("`no_ext` only applies to files, use `dir \"...\"` instead".to_string())
+        );
+    }
+
+    #[test]
+    fn keeps_confinement() {
+        assert!(lexical::<ExistsMatcher>("../outside.bin", "")
+            .unwrap_err()
+            .starts_with("\"../outside.bin\" leads outside the root"));
+    }
+
+    #[cfg(feature = "win")]
+    #[test]
+    fn keeps_names() {
+        assert_eq!(
+            lexical::<ValidDirMatcher>("data/com1", ""),
+            Err("reserved device name: \"com1\"".to_string())
+        );
+    }
+}
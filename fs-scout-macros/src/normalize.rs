@@ -0,0 +1,117 @@
+use proc_macro2::Span;
+use std::path::{Path, PathBuf};
+
+use crate::lint::Lint;
+
+/// A path split into its parts according to the rules of one platform.
+#[derive(PartialEq, Eq)]
+struct Normalized {
+    /// Drive prefix like `C:`, only recognized with Windows rules.
+    prefix: String,
+    rooted: bool,
+    parts: Vec<String>,
+    trailing: bool,
+}
+
+impl Normalized {
+    fn render(&self, windows: bool) -> String {
+        let separator = if windows { "\\" } else { "/" };
+        let mut rendered = self.prefix.clone();
+        if self.rooted {
+            rendered.push_str(separator);
+        }
+        rendered.push_str(&self.parts.join(separator));
+        if self.trailing && !self.parts.is_empty() {
+            rendered.push_str(separator);
+        }
+        if rendered.is_empty() {
+            rendered.push('.');
+        }
+        rendered
+    }
+}
+
+/// Lexically normalizes a path: removes `.` components, collapses repeated separators and folds `..`.
+///
+/// `name/..` is only folded, if `name` is an existing directory and not a symlink, as otherwise
+/// folding could change, which item the path refers to. Paths starting with two separators
+/// (e.g. UNC paths like `\\server\share`) are left unchanged.
+fn normalize(path: &str, windows: bool) -> Option<Normalized> {
+    let is_separator = |c: char| c == '/' || (windows && c == '\\');
+    if path.starts_with(is_separator) && path[1..].starts_with(is_separator) {
+        return None;
+    }
+
+    let mut rest = path;
+    let mut prefix = String::new();
+    if windows {
+        let bytes = path.as_bytes();
+        if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+            prefix = path[..2].to_string();
+            rest = &path[2..];
+        }
+    }
+    let rooted = rest.starts_with(is_separator);
+    let trailing = rest.ends_with(is_separator);
+
+    let mut normalized = Normalized {
+        prefix,
+        rooted,
+        parts: vec![],
+        trailing,
+    };
+    for part in rest.split(is_separator) {
+        match part {
+            "" | "." => (),
+            ".." => match normalized.parts.last() {
+                Some(last) if last != ".." && is_plain_dir(&normalized) => {
+                    normalized.parts.pop();
+                }
+                // The parent of the root is the root itself.
+                None if normalized.rooted => (),
+                _ => normalized.parts.push(part.to_string()),
+            },
+            name => normalized.parts.push(name.to_string()),
+        }
+    }
+    Some(normalized)
+}
+
+/// Checks, if the path normalized so far is an existing directory, which isn't a symlink.
+fn is_plain_dir(normalized: &Normalized) -> bool {
+    let mut path = PathBuf::from(&normalized.prefix);
+    if normalized.rooted {
+        path.push(std::path::MAIN_SEPARATOR_STR);
+    }
+    path.extend(&normalized.parts);
+    Path::new(&path)
+        .symlink_metadata()
+        .is_ok_and(|metadata| metadata.is_dir())
+}
+
+/// Normalizes a path with the rules of this platform.
+///
+/// Returns a lint, if the normalized path refers to a different item with the rules of the other platform,
+/// i.e. if `\` is a separator on only one of them.
+pub(crate) fn normalize_path(path: &str, span: Span) -> (String, Option<Lint>) {
+    let windows = cfg!(windows);
+    let (Some(native), Some(other)) = (normalize(path, windows), normalize(path, !windows)) else {
+        return (path.to_string(), None);
+    };
+    let normalized = native.render(windows);
+    let lint = (normalized != path && native != other).then(|| {
+        let (platform, reason) = if windows {
+            ("Unix", "`\\` is part of a name there")
+        } else {
+            ("Windows", "`\\` is a separator there")
+        };
+        Lint::new(
+            format!(
+                "\"{path}\" is normalized to \"{normalized}\", which refers to a different item on {platform} (\"{}\"), as {reason}",
+                other.render(!windows)
+            ),
+            span,
+        )
+    });
+    (normalized, lint)
+}
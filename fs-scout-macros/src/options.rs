@@ -0,0 +1,188 @@
+use proc_macro2::Span;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Ident, LitStr, Token,
+};
+
+use crate::{
+    access::Access,
+    condition::Condition,
+    digest::{Algorithm, Pin},
+    entry::EntryKind,
+    format::Format,
+    magic::FileKind,
+    separator::Separator,
+    size::SizeBound,
+    symlink::SymlinkPolicy,
+};
+
+/// Optional arguments following the path literal, e.g. `exists!("a.bin", sha256 = "...")`.
+#[derive(Default)]
+pub(crate) struct ScoutOptions {
+    pub(crate) pins: Vec<Pin>,
+    pub(crate) min_size: Option<SizeBound>,
+    pub(crate) max_size: Option<SizeBound>,
+    pub(crate) kind: Option<(FileKind, Span)>,
+    pub(crate) format: Option<Format>,
+    /// Explanation appended to any error.
+    pub(crate) message: Option<LitStr>,
+    /// Explicit kind of the entry, given before the path instead of guessing it from the extension.
+    pub(crate) entry: Option<(EntryKind, Span)>,
+    /// Access rights of the current user, which are required.
+    pub(crate) access: Vec<(Access, Span)>,
+    /// Marks a file path without an extension (e.g. `"Makefile"`).
+    pub(crate) no_ext: Option<Span>,
+    /// Expands to the lexically normalized path instead of the literal.
+    pub(crate) normalize: bool,
+    /// Separator written into the expansion.
+    pub(crate) sep: Option<Separator>,
+    /// Treatment of symlinks along the path, following them by default.
+    pub(crate) symlinks: Option<SymlinkPolicy>,
+    /// Condition, under which the path is checked against the filesystem.
+    pub(crate) when: Option<Condition>,
+}
+
+impl ScoutOptions {
+    /// Checks, if any option requires reading the contents of the path.
+    pub(crate) fn reads_contents(&self) -> bool {
+        !self.pins.is_empty() || self.kind.is_some() || self.format.is_some()
+    }
+
+    pub(crate) fn constrains_size(&self) -> bool {
+        self.min_size.is_some() || self.max_size.is_some()
+    }
+
+    /// Appends the custom message given with `message = "..."` to every error.
+    pub(crate) fn explain(&self, errors: Error) -> Error {
+        let Some(message) = &self.message else {
+            return errors;
+        };
+        errors
+            .into_iter()
+            .map(|e| Error::new(e.span(), format!("{e}: {}", message.value())))
+            .reduce(|mut errors, e| {
+                errors.combine(e);
+                errors
+            })
+            .expect("should contain at least one error")
+    }
+}
+
+impl Parse for ScoutOptions {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Self::parse_with(input, |_, _| Ok(false))
+    }
+}
+
+impl ScoutOptions {
+    /// Parses the common options, passing any other key to `extra`, which returns `false` for unknown keys.
+    /// This allows a single macro to accept additional options.
+    pub(crate) fn parse_with(
+        input: ParseStream,
+        mut extra: impl FnMut(&Ident, ParseStream) -> syn::Result<bool>,
+    ) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let key = input.parse::<Ident>()?;
+            if let Some(algorithm) = Algorithm::from_ident(&key) {
+                if options.pins.iter().any(|pin| pin.algorithm == algorithm) {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                // A bare algorithm name asks for the digest to be printed.
+                let expected = if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    Some(input.parse::<LitStr>()?)
+                } else {
+                    None
+                };
+                options
+                    .pins
+                    .push(Pin::new(algorithm, expected, key.span())?);
+            } else if key == "min_size" || key == "max_size" {
+                let bound = if key == "min_size" {
+                    &mut options.min_size
+                } else {
+                    &mut options.max_size
+                };
+                if bound.is_some() {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                input.parse::<Token![=]>()?;
+                *bound = Some(input.parse::<SizeBound>()?);
+                if let (Some(min), Some(max)) = (&options.min_size, &options.max_size) {
+                    if min.bytes > max.bytes {
+                        return Err(Error::new(
+                            max.span,
+                            format!(
+                                "min_size of {} bytes exceeds max_size of {} bytes",
+                                min.bytes, max.bytes
+                            ),
+                        ));
+                    }
+                }
+            } else if key == "kind" {
+                if options.kind.is_some() {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                input.parse::<Token![=]>()?;
+                let kind = input.parse::<Ident>()?;
+                options.kind = Some((FileKind::from_ident(&kind)?, kind.span()));
+            } else if key == "format" {
+                if options.format.is_some() {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                input.parse::<Token![=]>()?;
+                options.format = Some(Format::from_ident(&input.parse::<Ident>()?)?);
+            } else if key == "message" {
+                if options.message.is_some() {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                input.parse::<Token![=]>()?;
+                options.message = Some(input.parse::<LitStr>()?);
+            } else if let Some(access) = Access::from_ident(&key) {
+                if options.access.iter().any(|&(a, _)| a == access) {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                options.access.push((access, key.span()));
+            } else if key == "no_ext" {
+                if options.no_ext.is_some() {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                options.no_ext = Some(key.span());
+            } else if key == "normalize" {
+                if options.normalize {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                options.normalize = true;
+            } else if key == "sep" {
+                if options.sep.is_some() {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                input.parse::<Token![=]>()?;
+                options.sep = Some(Separator::from_ident(&input.parse::<Ident>()?)?);
+            } else if key == "symlinks" {
+                if options.symlinks.is_some() {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                input.parse::<Token![=]>()?;
+                options.symlinks = Some(SymlinkPolicy::from_ident(&input.parse::<Ident>()?)?);
+            } else if key == "when" {
+                if options.when.is_some() {
+                    return Err(Error::new(key.span(), format!("duplicate option `{key}`")));
+                }
+                input.parse::<Token![=]>()?;
+                options.when = Some(input.parse::<Condition>()?);
+            } else if !extra(&key, input)? {
+                return Err(Error::new(key.span(), format!("unknown option `{key}`")));
+            }
+        }
+
+        Ok(options)
+    }
+}
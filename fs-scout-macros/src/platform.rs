@@ -0,0 +1,206 @@
+use proc_macro2::Span;
+use syn::Error;
+
+use crate::lint::Lint;
+
+/// Environment variable listing the profiles of the invoking crate, separated by commas.
+/// Set by `fs_scout::build::set_profiles` from a build script, so unlike features, they don't apply
+/// to other crates in the dependency graph.
+pub(crate) const PROFILES_VAR: &str = "FS_SCOUT_PROFILES";
+
+/// Stricter rules for literals, which a crate opts into.
+#[derive(Default)]
+pub(crate) struct Profiles {
+    /// Checks literals on Unix too and forbids `\`, which is part of a name there.
+    pub(crate) unix: bool,
+    /// Checks literals on Windows too.
+    pub(crate) windows: bool,
+    /// Forbids literals anchored on any checked platform.
+    pub(crate) portable: bool,
+}
+
+impl Profiles {
+    pub(crate) fn load(span: Span) -> syn::Result<Self> {
+        let mut profiles = Self::default();
+        let var = std::env::var(PROFILES_VAR).unwrap_or_default();
+        for profile in var.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match profile {
+                "unix" => profiles.unix = true,
+                "windows" => profiles.windows = true,
+                "portable" => profiles.portable = true,
+                other => {
+                    return Err(Error::new(
+                        span,
+                        format!(
+                            "unknown profile `{other}` in {PROFILES_VAR}, \
+                             expected one of: unix, windows, portable"
+                        ),
+                    ))
+                }
+            }
+        }
+        Ok(profiles)
+    }
+
+    /// The platform compiling the code and the ones enabled by the `unix` and `windows` profiles.
+    fn platforms(&self) -> Vec<Platform> {
+        let mut platforms = vec![if cfg!(windows) {
+            Platform::Windows
+        } else {
+            Platform::Unix
+        }];
+        for (platform, enabled) in [
+            (Platform::Unix, self.unix),
+            (Platform::Windows, self.windows),
+        ] {
+            if enabled && !platforms.contains(&platform) {
+                platforms.push(platform);
+            }
+        }
+        platforms
+    }
+}
+
+/// Platforms, whose path rules a literal is classified with.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Platform {
+    Unix,
+    Windows,
+}
+
+/// How a path is anchored on a platform.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Anchor {
+    Absolute,
+    /// `\dir` on Windows, relative to the drive of the current directory.
+    RootRelative,
+    /// `C:dir` on Windows, relative to the current directory on that drive.
+    DriveRelative,
+    Relative,
+}
+
+impl Platform {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Unix => "Unix",
+            Self::Windows => "Windows",
+        }
+    }
+
+    pub(crate) fn classify(self, path: &str) -> Anchor {
+        match self {
+            Self::Unix if path.starts_with('/') => Anchor::Absolute,
+            Self::Unix => Anchor::Relative,
+            Self::Windows => {
+                let is_separator = |c: char| c == '/' || c == '\\';
+                let mut chars = path.chars();
+                match (chars.next(), chars.next(), chars.next()) {
+                    // UNC and verbatim paths, e.g. `\\server\share` or `\\?\C:\`.
+                    (Some(a), Some(b), _) if is_separator(a) && is_separator(b) => Anchor::Absolute,
+                    (Some(a), ..) if is_separator(a) => Anchor::RootRelative,
+                    (Some(drive), Some(':'), next) if drive.is_ascii_alphabetic() => match next {
+                        Some(c) if is_separator(c) => Anchor::Absolute,
+                        _ => Anchor::DriveRelative,
+                    },
+                    _ => Anchor::Relative,
+                }
+            }
+        }
+    }
+}
+
+impl Anchor {
+    fn describe(self) -> &'static str {
+        match self {
+            Self::Absolute => "absolute",
+            Self::RootRelative => "relative to the current drive",
+            Self::DriveRelative => "relative to the current directory of its drive",
+            Self::Relative => "relative",
+        }
+    }
+}
+
+/// Warns about a literal, which is anchored differently on the checked platforms.
+pub(crate) fn lint_anchor(path: &str, profiles: &Profiles, span: Span) -> Option<Lint> {
+    let platforms = profiles.platforms();
+    let anchors = platforms
+        .iter()
+        .map(|platform| (platform, platform.classify(path)))
+        .collect::<Vec<_>>();
+    let (first, first_anchor) = anchors[0];
+    let (other, other_anchor) = anchors.iter().find(|(_, anchor)| *anchor != first_anchor)?;
+    Some(Lint::new(
+        format!(
+            "\"{path}\" is {} on {}, but {} on {}",
+            first_anchor.describe(),
+            first.name(),
+            other_anchor.describe(),
+            other.name()
+        ),
+        span,
+    ))
+}
+
+/// With the `portable` profile, a literal anchored on any checked platform is an error.
+pub(crate) fn try_portable(path: &str, profiles: &Profiles, span: Span) -> syn::Result<()> {
+    if !profiles.portable {
+        return Ok(());
+    }
+    match profiles
+        .platforms()
+        .into_iter()
+        .map(|platform| (platform, platform.classify(path)))
+        .find(|(_, anchor)| *anchor != Anchor::Relative)
+    {
+        Some((platform, anchor)) => Err(Error::new(
+            span,
+            format!(
+                "\"{path}\" is {} on {}, but the `portable` profile only allows relative paths",
+                anchor.describe(),
+                platform.name()
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lint_anchor, try_portable, Profiles};
+    use proc_macro2::Span;
+
+    #[test]
+    fn host_only_by_default() {
+        let profiles = Profiles::default();
+        assert!(lint_anchor("/etc/app.toml", &profiles, Span::call_site()).is_none());
+        assert!(try_portable("/etc/app.toml", &profiles, Span::call_site()).is_ok());
+    }
+
+    #[test]
+    fn checked_platforms() {
+        let profiles = Profiles {
+            unix: true,
+            windows: true,
+            ..Profiles::default()
+        };
+        assert!(lint_anchor("/etc/app.toml", &profiles, Span::call_site()).is_some());
+        assert!(lint_anchor("C:dir", &profiles, Span::call_site()).is_some());
+        assert!(lint_anchor("dir/app.toml", &profiles, Span::call_site()).is_none());
+    }
+
+    #[test]
+    fn portable() {
+        let profiles = Profiles {
+            windows: true,
+            portable: true,
+            ..Profiles::default()
+        };
+        for path in ["/etc", "\\dir", "C:dir", "C:\\dir", "\\\\server\\share"] {
+            assert!(
+                try_portable(path, &profiles, Span::call_site()).is_err(),
+                "{path}"
+            );
+        }
+        assert!(try_portable("dir/app.toml", &profiles, Span::call_site()).is_ok());
+    }
+}
@@ -0,0 +1,95 @@
+use proc_macro2::Span;
+use std::path::{Component, Path, PathBuf};
+use syn::Error;
+
+/// Environment variable overriding the root, relative paths are confined to.
+/// A relative value is resolved against the package root.
+pub(crate) const ROOT_VAR: &str = "FS_SCOUT_ROOT";
+
+/// Root, relative paths are confined to. Defaults to the root of the package being compiled.
+pub(crate) fn root() -> PathBuf {
+    let package = package();
+    match std::env::var_os(ROOT_VAR) {
+        Some(root) => normalize(&package.join(root)),
+        None => package,
+    }
+}
+
+/// Root of the package being compiled.
+pub(crate) fn package() -> PathBuf {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(crate::cache::current_dir)
+}
+
+/// Checks, that a relative path stays inside the root, both lexically and after resolving symlinks.
+///
+/// Absolute paths aren't confined, as they name their location explicitly.
+pub(crate) fn try_confined(path: &Path, span: Span) -> syn::Result<()> {
+    try_confined_lexically(path, span)?;
+    if path.is_absolute() {
+        return Ok(());
+    }
+    try_resolves_within(path, span)
+}
+
+/// Checks, that a relative path stays inside the root without resolving symlinks.
+pub(crate) fn try_confined_lexically(path: &Path, span: Span) -> syn::Result<()> {
+    if path.is_absolute() {
+        return Ok(());
+    }
+    let root = root();
+    if normalize(&crate::absolute(path)).starts_with(&root) {
+        Ok(())
+    } else {
+        Err(Error::new(
+            span,
+            format!(
+                "\"{}\" leads outside the root \"{}\"",
+                path.display(),
+                root.display()
+            ),
+        ))
+    }
+}
+
+/// Resolves the nearest existing ancestor (or the path itself) and checks, that it's inside the root.
+pub(crate) fn try_resolves_within(path: &Path, span: Span) -> syn::Result<()> {
+    let absolute = normalize(&crate::absolute(path));
+    let Some(resolved) = absolute
+        .ancestors()
+        .find_map(|ancestor| ancestor.canonicalize().ok())
+    else {
+        return Ok(());
+    };
+    let root = root();
+    let root = root.canonicalize().unwrap_or(root);
+    if resolved.starts_with(&root) {
+        Ok(())
+    } else {
+        Err(Error::new(
+            span,
+            format!(
+                "\"{}\" resolves to \"{}\" outside the root \"{}\"",
+                path.display(),
+                resolved.display(),
+                root.display()
+            ),
+        ))
+    }
+}
+
+/// Removes `.` and resolves `..` components without accessing the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
@@ -0,0 +1,76 @@
+use proc_macro2::Span;
+use syn::{Error, Ident};
+
+use crate::lint::Lint;
+
+/// Separators written into the expansion, chosen with `sep = ...`.
+#[derive(Clone, Copy)]
+pub(crate) enum Separator {
+    /// The separator of the platform compiling the code.
+    Native,
+    Unix,
+    Windows,
+}
+
+impl Separator {
+    pub(crate) fn from_ident(ident: &Ident) -> syn::Result<Self> {
+        match ident.to_string().as_str() {
+            "native" => Ok(Self::Native),
+            "unix" => Ok(Self::Unix),
+            "windows" => Ok(Self::Windows),
+            other => Err(Error::new(
+                ident.span(),
+                format!("unknown separator `{other}`, expected one of: native, unix, windows"),
+            )),
+        }
+    }
+
+    fn char(self) -> char {
+        match self {
+            Self::Native => std::path::MAIN_SEPARATOR,
+            Self::Unix => '/',
+            Self::Windows => '\\',
+        }
+    }
+}
+
+/// Verbatim paths (`\\?\...`) disable all processing by Windows, so `/` isn't a separator in them.
+fn is_verbatim(path: &str) -> bool {
+    path.starts_with(r"\\?\")
+}
+
+/// Replaces both `/` and `\` with the chosen separator. Verbatim paths are left unchanged.
+pub(crate) fn rewrite(path: &str, separator: Separator) -> String {
+    if is_verbatim(path) {
+        return path.to_string();
+    }
+    path.replace(['/', '\\'], &separator.char().to_string())
+}
+
+/// Warns about a path using both `/` and `\`, which only Windows treats as the same.
+pub(crate) fn lint_mixed(path: &str, span: Span) -> Option<Lint> {
+    (!is_verbatim(path) && path.contains('/') && path.contains('\\')).then(|| {
+        Lint::new(
+            format!(
+                "\"{path}\" mixes `/` and `\\` as separators, but `\\` is part of a name on Unix; \
+                 use `sep = native` to rewrite them"
+            ),
+            span,
+        )
+    })
+}
+
+/// With the `unix` profile a backslash is always an error, as Unix treats it as part of a name.
+pub(crate) fn try_backslashes(path: &str, span: Span) -> syn::Result<()> {
+    if path.contains('\\') {
+        Err(Error::new(
+            span,
+            format!(
+                "\"{path}\" contains `\\`, which is part of a name on Unix; \
+                 use `/` or rewrite it with `sep = ...`"
+            ),
+        ))
+    } else {
+        Ok(())
+    }
+}
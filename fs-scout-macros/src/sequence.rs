@@ -0,0 +1,190 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::path::Path;
+use syn::{Error, LitStr};
+
+enum Part {
+    Literal(String),
+    /// `{n}`, one or more ASCII digits.
+    Number,
+    /// `{name}`, any non-empty text.
+    Name,
+}
+
+/// A file name pattern, e.g. `"{n}_{name}.sql"`.
+pub(crate) struct Pattern(Vec<Part>);
+
+impl Pattern {
+    pub(crate) fn new(lit: &LitStr) -> syn::Result<Self> {
+        let pattern = lit.value();
+        let mut parts = vec![];
+        let mut rest = pattern.as_str();
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_string()));
+            }
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| Error::new(lit.span(), "unclosed `{` in pattern"))?;
+            parts.push(match &rest[start + 1..start + end] {
+                "n" => Part::Number,
+                "name" => Part::Name,
+                other => {
+                    return Err(Error::new(
+                        lit.span(),
+                        format!(
+                            "unknown placeholder `{{{other}}}`, expected `{{n}}` or `{{name}}`"
+                        ),
+                    ))
+                }
+            });
+            rest = &rest[start + end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_string()));
+        }
+
+        let count = |f: fn(&Part) -> bool| parts.iter().filter(|p| f(p)).count();
+        if count(|p| matches!(p, Part::Number)) != 1 || count(|p| matches!(p, Part::Name)) > 1 {
+            return Err(Error::new(
+                lit.span(),
+                "pattern should contain `{n}` exactly once and `{name}` at most once",
+            ));
+        }
+        Ok(Self(parts))
+    }
+
+    /// Returns the digits of the number and the name (empty without `{name}`), if the file name matches.
+    fn matches<'a>(&self, file_name: &'a str) -> Option<(&'a str, String)> {
+        let mut number = None;
+        let mut name = String::new();
+        if Self::match_parts(&self.0, file_name, &mut number, &mut name) {
+            Some((number?, name))
+        } else {
+            None
+        }
+    }
+
+    fn match_parts<'a>(
+        parts: &[Part],
+        s: &'a str,
+        number: &mut Option<&'a str>,
+        name: &mut String,
+    ) -> bool {
+        match parts.split_first() {
+            None => s.is_empty(),
+            Some((Part::Literal(literal), rest)) => s
+                .strip_prefix(literal.as_str())
+                .is_some_and(|s| Self::match_parts(rest, s, number, name)),
+            Some((Part::Number, rest)) => {
+                let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+                *number = Some(&s[..digits]);
+                digits > 0 && Self::match_parts(rest, &s[digits..], number, name)
+            }
+            Some((Part::Name, rest)) => (1..=s.len()).filter(|&i| s.is_char_boundary(i)).any(|i| {
+                *name = s[..i].to_string();
+                Self::match_parts(rest, &s[i..], number, name)
+            }),
+        }
+    }
+}
+
+/// Lists the files in `dir` matching the pattern as a slice of `(number, name, path)` sorted by number.
+///
+/// Duplicate numbers and gaps in the sequence are errors.
+pub(crate) fn ordered_files(dir: &Path, pattern: &Pattern, span: Span) -> syn::Result<TokenStream> {
+    if !dir.is_dir() {
+        return Err(Error::new(
+            span,
+            format!("\"{}\" is not a directory", dir.display()),
+        ));
+    }
+    let entries = dir
+        .read_dir()
+        .and_then(Iterator::collect::<std::io::Result<Vec<_>>>);
+    let entries = entries.map_err(|e| {
+        Error::new(
+            span,
+            format!("can't list entries of \"{}\": {e}", dir.display()),
+        )
+    })?;
+
+    let mut errors = vec![];
+    let mut files = vec![];
+    for entry in entries {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let Some((digits, name)) = pattern.matches(&file_name) else {
+            continue;
+        };
+        let Ok(number) = digits.parse::<u64>() else {
+            errors.push(Error::new(
+                span,
+                format!(
+                    "\"{file_name}\": number {digits} is too large, the maximum is {}",
+                    u64::MAX
+                ),
+            ));
+            continue;
+        };
+        #[cfg(feature = "win")]
+        if let Err(e) = crate::check_name(&file_name, span) {
+            errors.push(Error::new(span, format!("\"{file_name}\": {e}")));
+        }
+        files.push((number, name, file_name));
+    }
+    files.sort();
+
+    for pair in files.windows(2) {
+        let ((previous, .., previous_file), (next, .., next_file)) = (&pair[0], &pair[1]);
+        if previous == next {
+            errors.push(Error::new(
+                span,
+                format!("duplicate number {next} in \"{previous_file}\" and \"{next_file}\""),
+            ));
+        } else if previous + 1 != *next {
+            errors.push(Error::new(
+                span,
+                format!(
+                    "gap in sequence between \"{previous_file}\" and \"{next_file}\", missing {}",
+                    match next - previous {
+                        2 => (previous + 1).to_string(),
+                        _ => format!("{} to {}", previous + 1, next - 1),
+                    }
+                ),
+            ));
+        }
+    }
+    crate::combine_errors(errors)?;
+
+    let files = files.iter().map(|(number, name, file_name)| {
+        let path = dir.join(file_name).display().to_string();
+        quote! { (#number, #name, #path) }
+    });
+    Ok(quote! { &[#(#files),*] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ordered_files, Pattern};
+    use proc_macro2::Span;
+    use syn::LitStr;
+
+    #[test]
+    fn too_large_numbers() {
+        let dir = std::env::temp_dir().join(format!("fs-scout-sequence-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("1_init.sql"), "").unwrap();
+        std::fs::write(dir.join("18446744073709551616_next.sql"), "").unwrap();
+        let pattern = Pattern::new(&LitStr::new("{n}_{name}.sql", Span::call_site())).unwrap();
+
+        assert_eq!(
+            ordered_files(&dir, &pattern, Span::call_site())
+                .unwrap_err()
+                .to_string(),
+            "\"18446744073709551616_next.sql\": number 18446744073709551616 is too large, \
+             the maximum is 18446744073709551615"
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
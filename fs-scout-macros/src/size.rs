@@ -0,0 +1,127 @@
+use proc_macro2::Span;
+use std::path::Path;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Lit,
+};
+
+const UNITS: [(&str, u64); 9] = [
+    ("B", 1),
+    ("KB", 1000),
+    ("MB", 1000 * 1000),
+    ("GB", 1000 * 1000 * 1000),
+    ("TB", 1000 * 1000 * 1000 * 1000),
+    ("KIB", 1024),
+    ("MIB", 1024 * 1024),
+    ("GIB", 1024 * 1024 * 1024),
+    ("TIB", 1024 * 1024 * 1024 * 1024),
+];
+
+/// A size limit, either given in bytes (`1024`) or with a unit (`"50MiB"`, `"2 KB"`).
+pub(crate) struct SizeBound {
+    pub(crate) bytes: u64,
+    pub(crate) span: Span,
+}
+
+impl Parse for SizeBound {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let bytes = match input.parse::<Lit>()? {
+            Lit::Int(int) => int.base10_parse::<u64>()?,
+            Lit::Str(s) => parse_size(&s.value()).ok_or_else(|| {
+                Error::new(
+                    span,
+                    format!(
+                        "invalid size \"{}\", expected e.g. \"512\", \"20KB\" or \"50MiB\"",
+                        s.value()
+                    ),
+                )
+            })?,
+            _ => {
+                return Err(Error::new(
+                    span,
+                    "expected size as integer or string literal",
+                ))
+            }
+        };
+        Ok(Self { bytes, span })
+    }
+}
+
+fn parse_size(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number = number.parse::<u64>().ok()?;
+    let unit = unit.trim().to_ascii_uppercase();
+    if unit.is_empty() {
+        return Some(number);
+    }
+    let (_, multiplier) = UNITS.iter().find(|(name, _)| *name == unit)?;
+    number.checked_mul(*multiplier)
+}
+
+/// Size of a file, or the summed size of all files in a directory tree.
+pub(crate) fn size_of(path: &Path) -> std::io::Result<u64> {
+    let metadata = path.metadata()?;
+    if metadata.is_dir() {
+        dir_size(path)
+    } else {
+        Ok(metadata.len())
+    }
+}
+
+/// Symlinks inside the tree are not followed, so they can't cause cycles.
+fn dir_size(path: &Path) -> std::io::Result<u64> {
+    let mut total = 0;
+    for entry in path.read_dir()? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        total += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+    Ok(total)
+}
+
+pub(crate) fn try_size(
+    path: &Path,
+    min: Option<&SizeBound>,
+    max: Option<&SizeBound>,
+    span: Span,
+) -> syn::Result<()> {
+    let size = size_of(path).map_err(|e| {
+        Error::new(
+            span,
+            format!("can't determine size of \"{}\": {e}", path.display()),
+        )
+    })?;
+
+    if let Some(min) = min {
+        if size < min.bytes {
+            return Err(Error::new(
+                min.span,
+                format!(
+                    "\"{}\" is {size} bytes, which is less than min_size of {} bytes",
+                    path.display(),
+                    min.bytes
+                ),
+            ));
+        }
+    }
+    if let Some(max) = max {
+        if size > max.bytes {
+            return Err(Error::new(
+                max.span,
+                format!(
+                    "\"{}\" is {size} bytes, which exceeds max_size of {} bytes",
+                    path.display(),
+                    max.bytes
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
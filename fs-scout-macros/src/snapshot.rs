@@ -0,0 +1,357 @@
+use proc_macro2::Span;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use syn::Error;
+
+use crate::{cache::Lookup, entry::EntryKind, lint::Lint};
+
+/// Environment variable naming the snapshot, absolute paths are checked against instead of the live disk.
+/// A relative value is resolved against the package root.
+pub(crate) const SNAPSHOT_VAR: &str = "FS_SCOUT_SNAPSHOT";
+
+/// A recorded filesystem layout, e.g. of a deployment image.
+///
+/// Every line is `dir`, `file` or `other` followed by an absolute path like `/etc/app.toml`. Ancestors of
+/// a listed path are directories, even if they aren't listed themselves. Empty lines and lines starting
+/// with `#` are ignored.
+///
+/// Paths only need a root, as on Windows hosts `/etc/app.toml` is relative to the current drive.
+pub(crate) struct Snapshot {
+    /// Absolute path of the snapshot, the expansion depends on.
+    pub(crate) file: PathBuf,
+    entries: HashMap<PathBuf, Lookup>,
+}
+
+/// Checks, if a path is checked against a snapshot instead of the live disk.
+pub(crate) fn applies(path: &Path) -> bool {
+    path.has_root() && std::env::var_os(SNAPSHOT_VAR).is_some()
+}
+
+/// Checks, if a path exists, in the snapshot for absolute paths, if one is given, or on the live disk.
+pub(crate) fn exists(snapshot: Option<&Snapshot>, path: &Path) -> bool {
+    match snapshot {
+        Some(snapshot) if path.has_root() => snapshot.lookup(path) != Lookup::Missing,
+        _ => matches!(path.try_exists(), Ok(true)),
+    }
+}
+
+impl Snapshot {
+    fn parse(file: PathBuf, text: &str) -> Result<Self, String> {
+        let mut entries = HashMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (kind, path) = line.split_once(' ').unwrap_or((line, ""));
+            let lookup = match kind {
+                "dir" => Lookup::Dir,
+                "file" => Lookup::File,
+                "other" => Lookup::Other,
+                other => {
+                    return Err(format!(
+                        "line {}: unknown kind `{other}`, expected one of: dir, file, other",
+                        i + 1
+                    ))
+                }
+            };
+            let path = Path::new(path.trim_start());
+            if !path.has_root() {
+                return Err(format!(
+                    "line {}: \"{}\" is not absolute",
+                    i + 1,
+                    path.display()
+                ));
+            }
+            for ancestor in path.ancestors().skip(1) {
+                entries.entry(ancestor.to_path_buf()).or_insert(Lookup::Dir);
+            }
+            entries.insert(path.to_path_buf(), lookup);
+        }
+        Ok(Self { file, entries })
+    }
+
+    /// Loads the snapshot named by [`SNAPSHOT_VAR`], if it's set.
+    pub(crate) fn load(span: Span) -> syn::Result<Option<Arc<Self>>> {
+        let Some(file) = std::env::var_os(SNAPSHOT_VAR) else {
+            return Ok(None);
+        };
+        let file = crate::root::package().join(file);
+        crate::cache::snapshot(|| {
+            let text = std::fs::read_to_string(&file).map_err(|e| e.to_string())?;
+            Self::parse(file.clone(), &text)
+        })
+        .map(Some)
+        .map_err(|e| {
+            Error::new(
+                span,
+                format!("can't load the snapshot \"{}\": {e}", file.display()),
+            )
+        })
+    }
+
+    /// Looks up, what a path refers to in the snapshot.
+    pub(crate) fn lookup(&self, path: &Path) -> Lookup {
+        match self.entries.get(path) {
+            Some(&lookup) => lookup,
+            None if path.parent().is_none() => Lookup::Dir,
+            None => Lookup::Missing,
+        }
+    }
+
+    /// Lists the recorded paths matching a glob pattern.
+    pub(crate) fn matching(&self, pattern: &str, span: Span) -> syn::Result<Vec<&Path>> {
+        let pattern = glob::Pattern::new(pattern)
+            .map_err(|e| Error::new(span, format!("invalid glob pattern: {e}")))?;
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..glob::MatchOptions::new()
+        };
+        let mut matches = self
+            .entries
+            .keys()
+            .filter(|path| pattern.matches_path_with(path, options))
+            .map(PathBuf::as_path)
+            .collect::<Vec<_>>();
+        matches.sort();
+        Ok(matches)
+    }
+
+    /// Lists the names of the recorded direct children of a directory, sorted.
+    pub(crate) fn children(&self, dir: &Path) -> Vec<String> {
+        let mut names = self
+            .entries
+            .keys()
+            .filter(|path| path.parent() == Some(dir))
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Checks the kind of a recorded entry against its annotation. Missing entries pass.
+    ///
+    /// Snapshots follow symlinks, so `symlink` can't be checked.
+    pub(crate) fn try_entry(&self, path: &Path, kind: EntryKind, span: Span) -> syn::Result<()> {
+        let expected = match kind {
+            EntryKind::File => Lookup::File,
+            EntryKind::Dir => Lookup::Dir,
+            EntryKind::Fifo | EntryKind::Socket => Lookup::Other,
+            EntryKind::Symlink => {
+                return Err(Error::new(
+                    span,
+                    "symlinks aren't recorded in snapshots, as they're followed",
+                ))
+            }
+        };
+        let actual = self.lookup(path);
+        if actual == expected || actual == Lookup::Missing {
+            Ok(())
+        } else {
+            Err(Error::new(
+                span,
+                format!(
+                    "\"{}\" is {} in the snapshot, expected {}",
+                    path.display(),
+                    describe(actual),
+                    EntryKind::describe(Some(kind))
+                ),
+            ))
+        }
+    }
+
+    /// Warns, if the live disk disagrees with the snapshot about a path or, failing that, one of its ancestors.
+    ///
+    /// Ancestors matter for paths, which don't need to exist, like the ones of `valid_file`.
+    pub(crate) fn lint_diff(&self, path: &Path, span: Span) -> Option<Lint> {
+        path.ancestors().find_map(|ancestor| {
+            let recorded = self.lookup(ancestor);
+            let live = crate::cache::lookup(ancestor);
+            (recorded != live).then(|| {
+                Lint::new(
+                    format!(
+                        "\"{}\" is {} in the snapshot, but {} on disk",
+                        ancestor.display(),
+                        describe(recorded),
+                        describe(live)
+                    ),
+                    span,
+                )
+            })
+        })
+    }
+}
+
+fn describe(lookup: Lookup) -> &'static str {
+    match lookup {
+        Lookup::Missing => "missing",
+        Lookup::Dir => "a directory",
+        Lookup::File => "a regular file",
+        Lookup::Other => "an entry of another kind",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{exists, Lookup, Snapshot};
+    use crate::{
+        dirs::{self, Bounds, Expected},
+        options::ScoutOptions,
+        ScoutData, ValidFileMatcher,
+    };
+    use proc_macro2::Span;
+    use std::path::{Path, PathBuf};
+    use syn::LitStr;
+
+    fn parse(text: &str) -> Result<Snapshot, String> {
+        Snapshot::parse(PathBuf::from("/fs-scout.lock"), text)
+    }
+
+    #[test]
+    fn lookups() {
+        let snapshot = parse("# comment\n\nfile /etc/app/config.toml\nother /dev/null\n").unwrap();
+        for (path, lookup) in [
+            ("/", Lookup::Dir),
+            ("/etc", Lookup::Dir),
+            ("/etc/app/", Lookup::Dir),
+            ("/etc/app/config.toml", Lookup::File),
+            ("/dev/null", Lookup::Other),
+            ("/etc/app/other.toml", Lookup::Missing),
+        ] {
+            assert!(snapshot.lookup(Path::new(path)) == lookup, "{path}");
+        }
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert_eq!(
+            parse("dir /etc\nlink /etc/app").err().unwrap(),
+            "line 2: unknown kind `link`, expected one of: dir, file, other"
+        );
+        assert_eq!(
+            parse("file etc/app.toml").err().unwrap(),
+            "line 1: \"etc/app.toml\" is not absolute"
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let dir = std::env::temp_dir().join(format!("fs-scout-diff-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), "").unwrap();
+        std::fs::write(dir.join("extra.txt"), "").unwrap();
+        let snapshot = parse(&format!(
+            "file {0}/a.txt\nfile {0}/new/b.txt\n",
+            dir.display()
+        ))
+        .unwrap();
+        let diff = |path: &str| {
+            snapshot
+                .lint_diff(&dir.join(path), Span::call_site())
+                .map(|lint| lint.message().to_string())
+        };
+
+        assert_eq!(diff("a.txt"), None);
+        assert_eq!(
+            diff("new/b.txt"),
+            Some(format!(
+                "\"{}\" is a regular file in the snapshot, but missing on disk",
+                dir.join("new/b.txt").display()
+            ))
+        );
+        assert_eq!(
+            diff("extra.txt"),
+            Some(format!(
+                "\"{}\" is missing in the snapshot, but a regular file on disk",
+                dir.join("extra.txt").display()
+            ))
+        );
+        // Only the parent of a file to create has to exist, so it's compared instead.
+        let parent = format!(
+            "\"{}\" is a directory in the snapshot, but missing on disk",
+            dir.join("new").display()
+        );
+        assert_eq!(diff("new/c.txt"), Some(parent.clone()));
+        let (_, lints) = ScoutData::<ValidFileMatcher>::scout_snapshot(
+            &dir.join("new/c.txt"),
+            &ScoutOptions::default(),
+            &snapshot,
+            true,
+            Span::call_site(),
+        )
+        .unwrap();
+        assert_eq!(
+            lints.iter().map(|lint| lint.message()).collect::<Vec<_>>(),
+            [parent]
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn candidates() {
+        let snapshot = parse("file /etc/myapp/config.toml\n").unwrap();
+        assert!(exists(Some(&snapshot), Path::new("/etc/myapp/config.toml")));
+        assert!(exists(Some(&snapshot), Path::new("/etc/myapp")));
+        assert!(!exists(Some(&snapshot), Path::new("/etc/myapp/local.toml")));
+        // The live disk is ignored for absolute paths, but still used for relative ones.
+        let live = std::env::temp_dir();
+        assert!(!exists(Some(&snapshot), &live));
+        assert!(exists(None, &live));
+        assert!(exists(Some(&snapshot), Path::new("src/snapshot.rs")));
+    }
+
+    #[test]
+    fn contents() {
+        let snapshot =
+            parse("file /etc/app/config.toml\nfile /etc/app/extra.ini\ndir /etc/app/conf.d\n")
+                .unwrap();
+        assert_eq!(
+            snapshot.children(Path::new("/etc/app")),
+            ["conf.d", "config.toml", "extra.ini"]
+        );
+        assert!(snapshot.children(Path::new("/etc/app/conf.d")).is_empty());
+
+        let span = Span::call_site();
+        let contains = |dir: &str, expected: Expected, bounds: Bounds| {
+            dirs::try_contains_recorded(&snapshot, Path::new(dir), &expected, &bounds, span)
+                .map_err(|e| e.to_string())
+        };
+        let names = || Expected::Names(vec![LitStr::new("config.toml", span)]);
+        let pattern = || Expected::Pattern(LitStr::new("*.toml", span));
+        assert_eq!(contains("/etc/app", names(), Bounds::default()), Ok(()));
+        assert_eq!(
+            contains(
+                "/etc/app",
+                names(),
+                Bounds {
+                    only: true,
+                    ..Bounds::default()
+                }
+            ),
+            Err("\"/etc/app\" contains unexpected entries: \"conf.d\", \"extra.ini\"".to_string())
+        );
+        assert_eq!(
+            contains(
+                "/etc/app",
+                pattern(),
+                Bounds {
+                    min: Some((3, span)),
+                    ..Bounds::default()
+                }
+            ),
+            Err(
+                "\"/etc/app\" contains 1 entries matching \"*.toml\", expected at least 3"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            contains("/etc/app/config.toml", pattern(), Bounds::default()),
+            Err("\"/etc/app/config.toml\" is not a directory in the snapshot".to_string())
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use proc_macro2::Span;
+use std::path::{Path, PathBuf};
+use syn::{Error, Ident};
+
+/// How symlinks along the path are treated, given with `symlinks = ...`.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SymlinkPolicy {
+    /// Symlinks are followed, only dangling ones are reported.
+    #[default]
+    Follow,
+    /// No component of the path may be a symlink.
+    Deny,
+    /// Symlinks may only resolve to items inside the root.
+    WithinRoot,
+}
+
+impl SymlinkPolicy {
+    pub(crate) fn from_ident(ident: &Ident) -> syn::Result<Self> {
+        match ident.to_string().as_str() {
+            "follow" => Ok(Self::Follow),
+            "deny" => Ok(Self::Deny),
+            "within_root" => Ok(Self::WithinRoot),
+            other => Err(Error::new(
+                ident.span(),
+                format!(
+                    "unknown symlink policy `{other}`, expected one of: follow, deny, within_root"
+                ),
+            )),
+        }
+    }
+}
+
+/// Walks the existing prefix of the path, reporting dangling symlinks and symlinks violating the policy.
+pub(crate) fn try_symlinks(path: &Path, policy: SymlinkPolicy, span: Span) -> syn::Result<()> {
+    let mut prefix = PathBuf::new();
+    for component in path.components() {
+        prefix.push(component);
+        let Ok(metadata) = prefix.symlink_metadata() else {
+            break;
+        };
+        if !metadata.is_symlink() {
+            continue;
+        }
+        let target = prefix
+            .read_link()
+            .map_or_else(|_| "?".to_string(), |target| target.display().to_string());
+        if prefix.metadata().is_err() {
+            return Err(Error::new(
+                span,
+                format!(
+                    "\"{}\" is a dangling symlink to \"{target}\"",
+                    prefix.display()
+                ),
+            ));
+        }
+        if policy == SymlinkPolicy::Deny {
+            return Err(Error::new(
+                span,
+                format!(
+                    "\"{}\" is a symlink to \"{target}\", which `symlinks = deny` forbids",
+                    prefix.display()
+                ),
+            ));
+        }
+    }
+
+    if policy == SymlinkPolicy::WithinRoot {
+        crate::root::try_resolves_within(path, span)?;
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{try_symlinks, SymlinkPolicy};
+    use proc_macro2::Span;
+    use std::path::{Path, PathBuf};
+
+    fn links() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/fixtures/links")
+    }
+
+    fn symlinks(name: &str, policy: SymlinkPolicy) -> Result<(), String> {
+        try_symlinks(&links().join(name), policy, Span::call_site()).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn dangling() {
+        assert_eq!(
+            symlinks("dangling.toml", SymlinkPolicy::Follow),
+            Err(format!(
+                "\"{}\" is a dangling symlink to \"missing.toml\"",
+                links().join("dangling.toml").display()
+            ))
+        );
+        assert_eq!(symlinks("config.toml", SymlinkPolicy::Follow), Ok(()));
+        assert_eq!(symlinks("missing.toml", SymlinkPolicy::Deny), Ok(()));
+    }
+
+    #[test]
+    fn deny() {
+        assert_eq!(
+            symlinks("config.toml", SymlinkPolicy::Deny),
+            Err(format!(
+                "\"{}\" is a symlink to \"../config.toml\", which `symlinks = deny` forbids",
+                links().join("config.toml").display()
+            ))
+        );
+    }
+
+    #[test]
+    fn within_root() {
+        let error = symlinks("outside", SymlinkPolicy::WithinRoot).unwrap_err();
+        assert!(
+            error.ends_with(&format!(
+                "resolves to \"/dev/null\" outside the root \"{}\"",
+                env!("CARGO_MANIFEST_DIR")
+            )),
+            "{error}"
+        );
+    }
+}
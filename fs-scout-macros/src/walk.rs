@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+/// An item found while walking a directory tree.
+pub(crate) struct Entry {
+    /// Path relative to the walked directory, always separated with `/`.
+    pub(crate) relative: String,
+    pub(crate) path: PathBuf,
+    pub(crate) is_dir: bool,
+}
+
+/// Lists all entries in a directory tree, sorted by their relative path.
+///
+/// Symlinked directories are listed, but not descended into, so they can't cause cycles.
+pub(crate) fn entries(root: &Path) -> std::io::Result<Vec<Entry>> {
+    let mut entries = vec![];
+    collect(root, "", &mut entries)?;
+    entries.sort_by(|a, b| a.relative.cmp(&b.relative));
+    Ok(entries)
+}
+
+/// Lists all files in a directory tree, sorted by their relative path.
+pub(crate) fn files(root: &Path) -> std::io::Result<Vec<Entry>> {
+    Ok(entries(root)?.into_iter().filter(|e| !e.is_dir).collect())
+}
+
+fn collect(dir: &Path, prefix: &str, entries: &mut Vec<Entry>) -> std::io::Result<()> {
+    for entry in dir.read_dir()? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let relative = if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}/{name}")
+        };
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect(&entry.path(), &relative, entries)?;
+        }
+        let is_dir = file_type.is_dir() || file_type.is_symlink() && entry.path().is_dir();
+        entries.push(Entry {
+            relative,
+            path: entry.path(),
+            is_dir,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::path::Path;
+
+    #[test]
+    fn symlinked_dirs() {
+        let links = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/fixtures/links");
+        let entries = super::entries(&links).unwrap();
+        let css = entries.iter().find(|e| e.relative == "css").unwrap();
+        assert!(css.is_dir);
+        assert!(!entries.iter().any(|e| e.relative.starts_with("css/")));
+        assert!(super::files(&links)
+            .unwrap()
+            .iter()
+            .all(|e| e.relative != "css"));
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+
+/// A rule of Windows naming, which a single path component breaks.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum NameError<'a> {
+    /// Names can't end with `.` or ` `, as Windows strips them.
+    Trailing(char),
+    /// One of `<>:"/\|?*`.
+    InvalidChar(char),
+    /// `file:stream` addresses an NTFS alternate data stream instead of a file.
+    AlternateStream { file: &'a str, stream: &'a str },
+    /// Characters `U+0000` to `U+001F`.
+    ControlChar(char),
+    /// Device names like `CON` or `COM1`, even with an extension and spaces before it.
+    Reserved { device: &'a str, spaced: bool },
+}
+
+impl fmt::Display for NameError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Trailing(c) => write!(f, "invalid character in trailing position: '{c}'"),
+            Self::InvalidChar(c) => write!(f, "invalid character: '{c}'"),
+            Self::AlternateStream { file, stream } => write!(
+                f,
+                "`:` addresses the alternate data stream \"{stream}\" of \"{file}\" on NTFS"
+            ),
+            Self::ControlChar(c) => write!(f, "invalid control character: U+{:04X}", *c as u32),
+            Self::Reserved {
+                device,
+                spaced: false,
+            } => write!(f, "reserved device name: \"{device}\""),
+            Self::Reserved {
+                device,
+                spaced: true,
+            } => write!(
+                f,
+                "reserved device name: \"{device}\", as spaces before the extension are ignored"
+            ),
+        }
+    }
+}
+
+/// Device names, which are reserved regardless of case and extension.
+const DEVICES: [&str; 6] = ["CON", "PRN", "AUX", "NUL", "CONIN$", "CONOUT$"];
+
+/// Checks a single path component against the naming rules of current Windows versions.
+pub(crate) fn validate(name: &str) -> Result<(), NameError<'_>> {
+    if let Some(c) = name.chars().next_back().filter(|c| matches!(c, '.' | ' ')) {
+        return Err(NameError::Trailing(c));
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*'))
+    {
+        return match name.split_once(':') {
+            Some((file, stream)) if c == ':' && !file.is_empty() => {
+                Err(NameError::AlternateStream { file, stream })
+            }
+            _ => Err(NameError::InvalidChar(c)),
+        };
+    }
+    if let Some(c) = name.chars().find(|&c| c < ' ') {
+        return Err(NameError::ControlChar(c));
+    }
+
+    // Everything from the first dot is an extension, spaces before it are ignored.
+    let stem = name.split('.').next().unwrap_or_default();
+    let device = stem.trim_end_matches(' ');
+    if is_device(device) {
+        return Err(NameError::Reserved {
+            device,
+            spaced: device.len() != stem.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks for `CON`, `PRN`, `AUX`, `NUL`, `CONIN$`, `CONOUT$` and `COM`/`LPT` followed by `1`-`9`, `¹`, `²` or `³`.
+fn is_device(stem: &str) -> bool {
+    if DEVICES
+        .iter()
+        .any(|device| device.eq_ignore_ascii_case(stem))
+    {
+        return true;
+    }
+    let (Some(prefix), Some(number)) = (stem.get(..3), stem.get(3..)) else {
+        return false;
+    };
+    let mut digits = number.chars();
+    (prefix.eq_ignore_ascii_case("COM") || prefix.eq_ignore_ascii_case("LPT"))
+        && matches!(digits.next(), Some('1'..='9' | '¹' | '²' | '³'))
+        && digits.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate, NameError};
+
+    #[test]
+    fn allowed() {
+        for name in [
+            "file.txt",
+            ".hidden",
+            "COM0",
+            "lpt0.log",
+            "CONSOLE",
+            "console.txt",
+            "COM10",
+            "NULL",
+            "AUXILIARY.md",
+            "con_",
+            "CONIN",
+            "ÄÖÜ ß.txt",
+        ] {
+            assert_eq!(validate(name), Ok(()), "{name}");
+        }
+    }
+
+    #[test]
+    fn trailing() {
+        assert_eq!(validate("name."), Err(NameError::Trailing('.')));
+        assert_eq!(validate("name "), Err(NameError::Trailing(' ')));
+        assert_eq!(validate("NUL."), Err(NameError::Trailing('.')));
+    }
+
+    #[test]
+    fn invalid_chars() {
+        for c in ['<', '>', '"', '/', '\\', '|', '?', '*'] {
+            assert_eq!(
+                validate(&format!("a{c}b")),
+                Err(NameError::InvalidChar(c)),
+                "{c}"
+            );
+        }
+        assert_eq!(validate(":b"), Err(NameError::InvalidChar(':')));
+    }
+
+    #[test]
+    fn alternate_stream() {
+        assert_eq!(
+            validate("file.txt:hidden"),
+            Err(NameError::AlternateStream {
+                file: "file.txt",
+                stream: "hidden"
+            })
+        );
+        assert_eq!(
+            validate("file:stream:$DATA"),
+            Err(NameError::AlternateStream {
+                file: "file",
+                stream: "stream:$DATA"
+            })
+        );
+    }
+
+    #[test]
+    fn control_chars() {
+        assert_eq!(validate("a\0b"), Err(NameError::ControlChar('\0')));
+        assert_eq!(validate("a\nb"), Err(NameError::ControlChar('\n')));
+        assert_eq!(validate("a\x1Fb"), Err(NameError::ControlChar('\x1F')));
+        assert_eq!(validate("a\x7Fb"), Ok(()));
+    }
+
+    #[test]
+    fn reserved() {
+        for (name, device) in [
+            ("CON", "CON"),
+            ("prn", "prn"),
+            ("Aux.txt", "Aux"),
+            ("nul.tar.gz", "nul"),
+            ("COM1", "COM1"),
+            ("com9.log", "com9"),
+            ("LPT5", "LPT5"),
+            ("COM¹", "COM¹"),
+            ("COM².txt", "COM²"),
+            ("lpt³", "lpt³"),
+            ("CONIN$", "CONIN$"),
+            ("conout$.txt", "conout$"),
+        ] {
+            assert_eq!(
+                validate(name),
+                Err(NameError::Reserved {
+                    device,
+                    spaced: false
+                }),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn reserved_spaced() {
+        assert_eq!(
+            validate("NUL .txt"),
+            Err(NameError::Reserved {
+                device: "NUL",
+                spaced: true
+            })
+        );
+        assert_eq!(
+            validate("com1  .log"),
+            Err(NameError::Reserved {
+                device: "com1",
+                spaced: true
+            })
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Helpers for build scripts, which pass information about the crate to the macros.
+
+/// Exports the active features of the crate being built, so `when = feature("...")` can be evaluated.
+///
+/// Call it from the build script of the crate using the macros (with `fs-scout` in its `[build-dependencies]`).
+///
+/// # Examples
+/// ```rust, no_run
+/// // in `main` of build.rs
+/// fs_scout::build::export_features();
+/// ```
+pub fn export_features() {
+    let mut features = std::env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase())
+        })
+        .collect::<Vec<_>>();
+    features.sort();
+    println!("cargo:rustc-env=FS_SCOUT_FEATURES={}", features.join(","));
+}
+
+/// Sets the root, relative paths in the macros are confined to. Defaults to the package root.
+///
+/// A relative `root` is resolved against the package root, e.g. `".."` for the root of a workspace.
+///
+/// # Examples
+/// ```rust, no_run
+/// // in `main` of build.rs
+/// fs_scout::build::set_root("..");
+/// ```
+pub fn set_root(root: impl AsRef<std::path::Path>) {
+    println!("cargo:rustc-env=FS_SCOUT_ROOT={}", root.as_ref().display());
+}
+
+/// Skips every check touching the filesystem, as if `FS_SCOUT_MODE=lexical` was set.
+///
+/// Meant for builds, which don't have the files, e.g. on docs.rs.
+///
+/// # Examples
+/// ```rust, no_run
+/// // in `main` of build.rs
+/// if std::env::var_os("DOCS_RS").is_some() {
+///     fs_scout::build::set_lexical();
+/// }
+/// ```
+pub fn set_lexical() {
+    println!("cargo:rustc-env=FS_SCOUT_MODE=lexical");
+}
+
+/// Checks absolute paths against a snapshot of the deployment layout instead of the live disk.
+///
+/// A relative `snapshot` is resolved against the package root. The snapshot is recorded with [`crate::snapshot::write`].
+///
+/// # Examples
+/// ```rust, no_run
+/// // in `main` of build.rs
+/// fs_scout::build::set_snapshot("fs-scout.lock");
+/// ```
+pub fn set_snapshot(snapshot: impl AsRef<std::path::Path>) {
+    println!(
+        "cargo:rustc-env=FS_SCOUT_SNAPSHOT={}",
+        snapshot.as_ref().display()
+    );
+}
+
+/// Opts the crate being built into stricter profiles: `unix`, `windows` or `portable`.
+///
+/// # Examples
+/// ```rust, no_run
+/// // in `main` of build.rs
+/// fs_scout::build::set_profiles(&["unix", "portable"]);
+/// ```
+pub fn set_profiles(profiles: &[&str]) {
+    println!("cargo:rustc-env=FS_SCOUT_PROFILES={}", profiles.join(","));
+}
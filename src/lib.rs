@@ -8,12 +8,30 @@
 //! files or directories forbidden by Windows). It is included in default feature for that very reason.
 //! If you don't plan to deploy to Windows and need to bypass this restriction, you can use flag
 //! `default-features = false` to disable this behaviour.
+//!
+//! The feature flags `"toml"`, `"json"`, `"yaml"` and `"ron"` enable the respective `format = ...` checks.
+//!
+//! # Options
+//! The options accepted by the macros and the environment variables they read are described
+//! in the [crate documentation of `fs_scout_macros`](fs_scout_macros#options).
+//!
+//! # Profiles
+//! Stricter rules for literals are opted into per crate with [`build::set_profiles`], so unlike features,
+//! they don't apply to other crates in the dependency graph:
+//! * `unix` checks literals for Unix too and makes a `\` in a path an error, as Unix treats it as part
+//!   of a file name. Use `sep = ...` to rewrite separators instead.
+//! * `windows` checks literals for Windows too.
+//! * `portable` forbids absolute paths, as judged by every checked platform.
 
 extern crate fs_scout_macros;
 
 pub use fs_scout_macros::*;
 
+pub mod build;
+pub mod snapshot;
+
 /// Creates a file using [`std::fs::File::create`]. During compiletime validates the input path.
+/// Accepts the same options as [`valid_file`] (e.g. `no_ext`).
 ///
 /// # Examples
 /// ```rust, no_run
@@ -21,6 +39,7 @@ pub use fs_scout_macros::*;
 /// #
 /// # fn main() -> Result<(), Box<dyn Error>> {
 /// use fs_scout::create_file;
+/// use std::fs::File;
 ///
 /// let result: std::io::Result<File> = create_file!("file.txt");
 ///
@@ -31,16 +50,17 @@ pub use fs_scout_macros::*;
 /// ```
 #[macro_export]
 macro_rules! create_file {
-    ($path:literal!) => {{
-        std::fs::File::create($crate::valid_file!($path))
+    ($path:literal! $(, $($option:tt)+)?) => {{
+        std::fs::File::create($crate::valid_file!($path $(, $($option)+)?))
             .expect(&format!("should be able to create a file at {}", $path))
     }};
-    ($path:literal) => {{
-        std::fs::File::create($crate::valid_file!($path))
+    ($path:literal $(, $($option:tt)+)?) => {{
+        std::fs::File::create($crate::valid_file!($path $(, $($option)+)?))
     }};
 }
 
-/// Opens a file using [`std::fs::File::open`]. During compiletime validates the input path.
+/// Opens a file using [`std::fs::File::open`]. During compiletime checks, that the file exists and is readable.
+/// Accepts the same options as [`exists`] (e.g. `sha256 = "..."`).
 ///
 /// # Examples
 /// ```rust, ignore
@@ -58,18 +78,17 @@ macro_rules! create_file {
 /// ```
 #[macro_export]
 macro_rules! open_file {
-    ($path:literal!) => {{
-        $crate::exists!($path);
-        std::fs::File::open($crate::valid_file!($path))
+    ($path:literal! $(, $($option:tt)+)?) => {{
+        std::fs::File::open($crate::exists!(file $path, readable $(, $($option)+)?))
             .expect(&format!("should be able to open a file at {}", $path))
     }};
-    ($path:literal) => {{
-        $crate::exists!($path);
-        std::fs::File::open($crate::valid_file!($path))
+    ($path:literal $(, $($option:tt)+)?) => {{
+        std::fs::File::open($crate::exists!(file $path, readable $(, $($option)+)?))
     }};
 }
 
-/// Reads a file using [`std::fs::File::open`]. During compiletime validates the input path.
+/// Reads a file using [`std::fs::File::open`]. During compiletime checks, that the file exists and is readable.
+/// Accepts the same options as [`exists`] (e.g. `sha256 = "..."`).
 ///
 /// # Examples
 /// ```rust, ignore
@@ -89,14 +108,12 @@ macro_rules! open_file {
 /// ```
 #[macro_export]
 macro_rules! read_file {
-    ($path:literal!) => {{
-        $crate::exists!($path);
-        &std::fs::read($crate::valid_file!($path))
+    ($path:literal! $(, $($option:tt)+)?) => {{
+        &std::fs::read($crate::exists!(file $path, readable $(, $($option)+)?))
             .expect(&format!("should be able to read a file at {}", $path))
     }};
-    ($path:literal) => {{
-        $crate::exists!($path);
-        &std::fs::read($crate::valid_file!($path))
+    ($path:literal $(, $($option:tt)+)?) => {{
+        &std::fs::read($crate::exists!(file $path, readable $(, $($option)+)?))
     }};
 }
 
@@ -128,7 +145,7 @@ macro_rules! write_file {
 }
 
 /// Creates a directory using [`std::fs::create_dir_all`]. During compiletime validates the input path.
-/// Checks, if the directory's ancestors exist.
+/// Checks, if the directory's ancestors exist and the parent is writable.
 ///
 /// [`std::fs::create_dir`] is not used, because it errors, if the directory already exists.
 /// If you need to use it, call `std::fs::create_dir(fs_scout::valid!("..."))` instead and handle the error.
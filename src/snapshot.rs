@@ -0,0 +1,84 @@
+//! Recording of snapshots, which absolute paths are checked against, if `FS_SCOUT_SNAPSHOT` is set.
+//!
+//! Every line of a snapshot is `dir`, `file` or `other` followed by an absolute path, e.g. `file /etc/app.toml`.
+
+use std::{fs, io, path::Path};
+
+/// Records the layout of a directory, e.g. of an extracted deployment image.
+///
+/// The directory becomes `/` in the snapshot, so `image/etc/app.toml` is recorded as `/etc/app.toml`.
+/// Symlinks are recorded as the item they point to, but symlinked directories aren't descended into.
+///
+/// # Examples
+/// ```rust, no_run
+/// # fn main() -> std::io::Result<()> {
+/// let snapshot = fs_scout::snapshot::record("image")?;
+/// assert!(snapshot.contains("file /etc/app.toml\n"));
+/// #   Ok(())
+/// # }
+/// ```
+pub fn record(dir: impl AsRef<Path>) -> io::Result<String> {
+    let dir = dir.as_ref();
+    let mut entries = vec![];
+    walk(dir, "", &mut entries)?;
+    entries.sort();
+
+    let mut snapshot = format!("# fs-scout snapshot of \"{}\"\n", dir.display());
+    for (path, kind) in entries {
+        snapshot.push_str(&format!("{kind} {path}\n"));
+    }
+    Ok(snapshot)
+}
+
+/// Records the layout of a directory with [`record`] and writes it to `snapshot`.
+///
+/// # Examples
+/// ```rust, no_run
+/// # fn main() -> std::io::Result<()> {
+/// fs_scout::snapshot::write("image", "fs-scout.lock")?;
+/// #   Ok(())
+/// # }
+/// ```
+pub fn write(dir: impl AsRef<Path>, snapshot: impl AsRef<Path>) -> io::Result<()> {
+    fs::write(snapshot, record(dir)?)
+}
+
+/// Collects the entries below `dir` as paths starting with `prefix`, always separated with `/`.
+fn walk(dir: &Path, prefix: &str, entries: &mut Vec<(String, &'static str)>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name
+            .to_str()
+            .filter(|name| !name.contains('\n'))
+            .ok_or_else(|| unrecordable(&path))?;
+        let recorded = format!("{prefix}/{name}");
+        // Dangling symlinks don't refer to any item, so they aren't recorded.
+        let Ok(metadata) = path.metadata() else {
+            continue;
+        };
+        let kind = if metadata.is_dir() {
+            if !entry.file_type()?.is_symlink() {
+                walk(&path, &recorded, entries)?;
+            }
+            "dir"
+        } else if metadata.is_file() {
+            "file"
+        } else {
+            "other"
+        };
+        entries.push((recorded, kind));
+    }
+    Ok(())
+}
+
+fn unrecordable(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "the name of \"{}\" can't be recorded, as it isn't valid UTF-8 or contains a line break",
+            path.display()
+        ),
+    )
+}
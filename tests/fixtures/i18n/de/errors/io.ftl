@@ -0,0 +1 @@
+oops = de
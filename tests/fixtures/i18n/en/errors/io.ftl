@@ -0,0 +1 @@
+oops = en
@@ -0,0 +1 @@
+ALTER TABLE a ADD b INT;
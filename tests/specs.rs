@@ -1,4 +1,4 @@
-#![allow(unused)]
+#![allow(unused, clippy::let_unit_value)]
 
 use std::fs::{create_dir, read};
 
@@ -7,7 +7,7 @@ use fs_scout::*;
 #[test]
 fn example() {
     let file = exists!("Cargo.toml");
-    let dir = exists!("fs-scout-macros/src\\");
+    let dir = exists!("fs-scout-macros/src\\", sep = native);
     // let non_existant = exists!("a");
 
     // let reserved_name = valid_dir!("com1");
@@ -17,28 +17,42 @@ fn example() {
     // let trailing_dot = valid_file!("any.");
     // let trailing_space = valid_dir!("any ");
 
+    // Drive letters, `\` separators and verbatim prefixes only form paths on Windows,
+    // elsewhere they are names with characters forbidden by the `win` feature.
+    #[cfg(windows)]
     let abs_file_b = valid_file!("\\Letter.txt");
-    let abs_file_f = valid_file!("/Letter.txt");
+    #[cfg(unix)]
+    let abs_file_f = valid_file!("/tmp/Letter.txt");
+    #[cfg(windows)]
     let abs_dir_b = valid_dir!("\\user");
-    let abs_dir_f = valid_dir!("/user");
+    #[cfg(unix)]
+    let abs_dir_f = valid_dir!("/tmp/user");
 
+    #[cfg(windows)]
     let disk_abs_file_b = valid_file!("C:\\Letter.txt");
+    #[cfg(windows)]
     let disk_abs_file_f = valid_file!("C:/Letter.txt");
+    #[cfg(windows)]
     let disk_abs_dir_b = valid_dir!("C:\\user");
+    #[cfg(windows)]
     let disk_abs_dir_f = valid_dir!("C:/user");
 
+    #[cfg(windows)]
     let unc_disk_file = valid_file!("\\\\?\\C:\\Letter.txt\\");
+    #[cfg(windows)]
     let unc_disk_dir = valid_dir!("\\\\?\\C:\\user\\");
 
+    #[cfg(windows)]
     let cur_file_b = valid_file!(".\\cur.bin");
     let cur_file_f = valid_file!("./cur.bin");
+    #[cfg(windows)]
     let cur_dir_b = valid_dir!(".\\cur");
     let cur_dir_f = valid_dir!("./cur");
 
-    let parent_file_b = valid_file!("..\\parent.bin");
-    let parent_file_f = valid_file!("../parent.bin");
-    let parent_dir_b = valid_dir!("..\\parent");
-    let parent_dir_f = valid_dir!("../parent");
+    // let parent_file_b = valid_file!("..\\parent.bin");
+    // let parent_file_f = valid_file!("../parent.bin");
+    // let parent_dir_b = valid_dir!("..\\parent");
+    // let parent_dir_f = valid_dir!("../parent");
 
     let hidden_dir = valid_dir!(".hidden");
     let hidden_file = valid_file!(".hidden.bin");
@@ -58,3 +72,309 @@ fn example() {
 
     println!("{}", String::from_utf8_lossy(read_file));
 }
+
+#[test]
+fn checksum() {
+    let sha256 = exists!(
+        "tests/fixtures/golden.bin",
+        sha256 = "f7edcdf2037ecd4ae250d46ee57cb03573eea8d88657bccef3c5631a9eb03935"
+    );
+    let blake3 = exists!(
+        "tests/fixtures/golden.bin",
+        blake3 = "1aa945dfa35157bdbb0f54ca9df467d8ee1a8d937b1b479cb2b1c23e7cd8a18a"
+    );
+    let crc32 = exists!("tests/fixtures/golden.bin", crc32 = "D7126123");
+    // let mismatch = exists!("tests/fixtures/golden.bin", crc32 = "00000000");
+    // let print_digest = exists!("tests/fixtures/golden.bin", sha256);
+    // let dir_digest = exists!("tests/fixtures", crc32 = "00000000");
+
+    let embedded: &[u8] = embed_file!("tests/fixtures/golden.bin", crc32 = "d7126123");
+    let read_file = read_file!("tests/fixtures/golden.bin"!, crc32 = "d7126123");
+    let opened_file = open_file!("tests/fixtures/golden.bin"!, crc32 = "d7126123");
+
+    assert_eq!(embedded, read_file.as_slice());
+}
+
+#[test]
+fn size() {
+    let bounded_file = exists!("tests/fixtures/golden.bin", min_size = 1, max_size = "1KiB");
+    let bounded_dir = exists!("tests/fixtures", max_size = "1MiB");
+    let missing_file = valid_file!("tests/fixtures/missing.bin", min_size = 1);
+    // let too_small = exists!("tests/fixtures/golden.bin", min_size = "1MB");
+    // let too_big = exists!("tests/fixtures", max_size = 1);
+    // let invalid_unit = exists!("tests/fixtures", max_size = "1 parsec");
+    // let min_over_max = exists!("tests/fixtures/golden.bin", min_size = 5, max_size = 1);
+}
+
+#[test]
+fn kind() {
+    let png = exists!("tests/fixtures/pixel.png", kind = png);
+    let utf8 = exists!("tests/fixtures/golden.bin", kind = utf8);
+    let embedded_png: &[u8] = embed_file!("tests/fixtures/pixel.png", kind = png);
+    // let wrong_kind = exists!("tests/fixtures/pixel.png", kind = jpeg);
+    // let unknown_kind = exists!("tests/fixtures/pixel.png", kind = jpg);
+}
+
+#[test]
+#[cfg(all(feature = "toml", feature = "json"))]
+fn format() {
+    let toml = exists!("tests/fixtures/config.toml", format = toml);
+    let json = read_file!("tests/fixtures/config.json"!, format = json);
+    // let not_json = exists!("tests/fixtures/config.toml", format = json);
+    // let unknown_format = exists!("tests/fixtures/config.toml", format = ini);
+}
+
+#[test]
+fn metadata() {
+    const SIZE: u64 = file_size!("tests/fixtures/golden.bin");
+    const LINES: usize = file_lines!("tests/fixtures/config.toml");
+    const MTIME: u64 = file_mtime!("tests/fixtures/golden.bin");
+    const SHA256: [u8; 32] = file_hash!("tests/fixtures/golden.bin");
+    const CRC32: [u8; 4] = file_hash!(crc32, "tests/fixtures/golden.bin");
+    // const DIR_SIZE: u64 = file_size!("tests/fixtures");
+    // const UNKNOWN_ALGORITHM: [u8; 16] = file_hash!(md5, "tests/fixtures/golden.bin");
+
+    assert_eq!(SIZE, 24);
+    assert_eq!(LINES, 3);
+    assert_eq!(SHA256[..4], [0xf7, 0xed, 0xcd, 0xf2]);
+    assert_eq!(CRC32, [0xd7, 0x12, 0x61, 0x23]);
+}
+
+#[test]
+fn assets() {
+    static ASSETS: &[(&str, &str, &[u8])] = asset_manifest!("tests/fixtures/static");
+    static PATHS: &[(&str, &str, &str)] = asset_manifest!("tests/fixtures/static", embed = false);
+    // static NOT_DIR: &[(&str, &str, &[u8])] = asset_manifest!("tests/fixtures/golden.bin");
+    // static DUPLICATE_EMBED: &[(&str, &str, &[u8])] =
+    //     asset_manifest!("tests/fixtures/static", embed = true, embed = false);
+
+    let i = ASSETS
+        .binary_search_by_key(&"css/site.css", |&(logical, ..)| logical)
+        .unwrap();
+    assert_eq!(ASSETS[i].1, "css/site.eac0e790.css");
+    assert_eq!(PATHS[i].2, "tests/fixtures/static/css/site.css");
+}
+
+#[test]
+fn absence() {
+    const _: &str = absent!(
+        "config/old.ini",
+        message = "settings were moved to Cargo.toml"
+    );
+    const _: &str = absent!("tests/**/*.orig");
+    // const _: &str = absent!("Cargo.toml", message = "should fail");
+    // const _: &str = absent!("tests/fixtures/*.bin");
+}
+
+#[test]
+fn dir_contents() {
+    let locales = dir_contains!("tests/fixtures/locales", ["en.ftl", "de.ftl"], only);
+    let ftl = dir_contains!("tests/fixtures/locales", "*.ftl", min = 2, max = 2, only);
+    let bin = dir_contains!("tests/fixtures", "*.bin");
+    let no_backups = dir_contains!("tests/fixtures", "*.bak", max = 0);
+    // let missing = dir_contains!("tests/fixtures/locales", ["fr.ftl"]);
+    // let unexpected = dir_contains!("tests/fixtures/locales", ["en.ftl"], only);
+    // let too_few = dir_contains!("tests/fixtures", "*.sql", min = 1);
+    // let min_over_max = dir_contains!("tests/fixtures", "*.bin", min = 2, max = 1);
+    // let duplicate_min = dir_contains!("tests/fixtures", "*.bin", min = 1, min = 2);
+    // let duplicate_only = dir_contains!("tests/fixtures/locales", ["en.ftl", "de.ftl"], only, only);
+}
+
+#[test]
+fn ordered() {
+    static MIGRATIONS: &[(u64, &str, &str)] =
+        ordered_files!("tests/fixtures/migrations", pattern = "{n}_{name}.sql");
+    // static MISSING_PATTERN: &[(u64, &str, &str)] = ordered_files!("tests/fixtures/migrations");
+    // static UNKNOWN_PLACEHOLDER: &[(u64, &str, &str)] =
+    //     ordered_files!("tests/fixtures/migrations", pattern = "{id}.sql");
+    // static DUPLICATE_PATTERN: &[(u64, &str, &str)] = ordered_files!(
+    //     "tests/fixtures/migrations",
+    //     pattern = "{n}_{name}.sql",
+    //     pattern = "{n}.sql"
+    // );
+
+    assert_eq!(
+        MIGRATIONS,
+        [
+            (1, "init", "tests/fixtures/migrations/0001_init.sql"),
+            (2, "add_b", "tests/fixtures/migrations/0002_add_b.sql"),
+        ]
+    );
+}
+
+#[test]
+fn consistency() {
+    static LOCALES: &[&str] = consistent_dirs!(
+        "tests/fixtures/i18n/*",
+        reference = "tests/fixtures/i18n/en"
+    );
+    // static INCONSISTENT: &[&str] = consistent_dirs!("tests/fixtures/*");
+    // static NO_MATCH: &[&str] = consistent_dirs!("tests/fixtures/missing/*");
+    // static DUPLICATE_REFERENCE: &[&str] = consistent_dirs!(
+    //     "tests/fixtures/i18n/*",
+    //     reference = "tests/fixtures/i18n/en",
+    //     reference = "tests/fixtures/i18n/de"
+    // );
+
+    assert_eq!(
+        LOCALES,
+        ["tests/fixtures/i18n/de", "tests/fixtures/i18n/en"]
+    );
+}
+
+#[test]
+fn candidates() {
+    const CONFIG: &str = first_existing!(
+        "tests/fixtures/config.yaml",
+        "tests/fixtures/config.toml",
+        "tests/fixtures/config.json"
+    );
+    const OPTIONAL: Option<&str> = maybe_exists!("tests/fixtures/local.toml");
+    const PRESENT: Option<&str> = maybe_exists!("tests/fixtures/golden.bin", max_size = "1 KiB");
+    // const NONE_EXIST: &str = first_existing!("tests/fixtures/a.toml", "tests/fixtures/b.toml");
+    // const TOO_BIG: Option<&str> = maybe_exists!("tests/fixtures/golden.bin", max_size = 1);
+
+    assert_eq!(CONFIG, "tests/fixtures/config.toml");
+    assert_eq!(OPTIONAL, None);
+    assert_eq!(PRESENT, Some("tests/fixtures/golden.bin"));
+}
+
+#[test]
+fn conditional() {
+    // Requires `fs_scout::build::export_features()` in the build script of the crate.
+    // const BUNDLED: &str = exists!("tests/fixtures/large.bin", when = feature("bundled-models"));
+    // const UNBUNDLED: &str = exists!("tests/fixtures/golden.bin", when = not(feature("bundled")));
+    // const UNKNOWN_CONDITION: &str = exists!("tests/fixtures/golden.bin", when = cfg(test));
+}
+
+#[test]
+fn entry_kinds() {
+    let release = exists!(dir "tests/fixtures/releases/v1.2");
+    let release_dir = valid_dir!(dir "tests/fixtures/releases/v1.2");
+    let notes = valid_file!("tests/fixtures/releases/v1.2/NOTES", no_ext);
+    let robots = exists!(file "tests/fixtures/static/robots");
+    // let not_a_dir = exists!(dir "tests/fixtures/golden.bin");
+    // let has_extension = valid_file!("tests/fixtures/golden.bin", no_ext);
+    // let conflicting = valid_file!(dir "tests/fixtures/releases/v1.2");
+}
+
+#[test]
+fn existing_components() {
+    let nested = valid_dir_all!("tests/fixtures/releases/v1.2/patches/next");
+    let notes = valid_file!(file "tests/fixtures/releases/v1.2/NOTES");
+    // let under_file = valid_dir_all!("tests/fixtures/golden.bin/out");
+    // let is_dir = valid_file!("tests/fixtures/releases/v1.2");
+    // let is_file = valid_dir!("tests/fixtures/static/robots");
+}
+
+#[test]
+fn access() {
+    let config = exists!("tests/fixtures/config.toml", readable, writable);
+    let fixtures = valid_dir!("tests/fixtures/generated");
+    // let script = exists!("tests/fixtures/config.toml", executable);
+    // let duplicate = exists!("tests/fixtures/config.toml", readable, readable);
+}
+
+#[test]
+fn symlinks() {
+    let config = exists!("tests/fixtures/config.toml", symlinks = deny);
+    let assets = exists!("tests/fixtures/static", symlinks = within_root);
+    // let outside = exists!("/etc", symlinks = within_root);
+    // Git checks symlinks out as plain files on Windows, unless `core.symlinks` is set.
+    #[cfg(unix)]
+    {
+        let linked = exists!("tests/fixtures/links/config.toml");
+        let linked_within = exists!("tests/fixtures/links/config.toml", symlinks = within_root);
+        let dangling_absent = valid_file!("tests/fixtures/links/new.toml");
+        // let dangling = valid_file!("tests/fixtures/links/dangling.toml");
+        // let dangling_exists = absent!("tests/fixtures/links/dangling.toml");
+        // let denied = exists!("tests/fixtures/links/config.toml", symlinks = deny);
+        // let escaping = exists!("tests/fixtures/links/outside", symlinks = within_root);
+    }
+    // let unknown_policy = exists!("tests/fixtures/config.toml", symlinks = ignore);
+}
+
+#[test]
+fn confinement() {
+    let inside = valid_file!("tests/../tests/fixtures/new.bin");
+    #[cfg(unix)]
+    let absolute = valid_dir!("/tmp/user");
+    // let outside = valid_file!("../../etc/passwd");
+    // let sibling = exists!("../fs-scout/Cargo.toml");
+}
+
+#[test]
+fn normalized() {
+    const CONFIG: &str = exists!("./tests//fixtures/../fixtures/./config.toml", normalize);
+    const UNCHANGED: &str = exists!("tests/fixtures/config.toml", normalize);
+
+    assert_eq!(CONFIG, "tests/fixtures/config.toml");
+    assert_eq!(UNCHANGED, "tests/fixtures/config.toml");
+}
+
+#[test]
+fn separators() {
+    const UNIX: &str = exists!("tests\\fixtures/config.toml", sep = unix);
+    const WINDOWS: &str = exists!("tests/fixtures/config.toml", sep = windows);
+    // const UNKNOWN: &str = exists!("tests/fixtures/config.toml", sep = dos);
+    const SIZE: u64 = file_size!("tests/fixtures/golden.bin", sep = windows);
+    static EMBEDDED: &[u8] = embed_file!("tests\\fixtures/golden.bin", sep = windows);
+    const NORMALIZED_LINES: usize = file_lines!("tests/./fixtures/config.toml", normalize);
+    const MAYBE: Option<&str> = maybe_exists!("tests\\fixtures\\config.toml", sep = unix);
+    const FIRST: &str = first_existing!(
+        "tests\\fixtures\\config.ini",
+        "tests\\fixtures\\config.toml",
+        sep = unix
+    );
+    static LOCALES: &[&str] = consistent_dirs!(
+        "tests\\fixtures\\i18n\\*",
+        reference = "tests\\fixtures\\i18n\\en",
+        sep = unix
+    );
+
+    assert_eq!(UNIX, "tests/fixtures/config.toml");
+    assert_eq!(WINDOWS, "tests\\fixtures\\config.toml");
+    assert_eq!(SIZE, 24);
+    assert_eq!(EMBEDDED.len(), 24);
+    assert_eq!(NORMALIZED_LINES, 3);
+    assert_eq!(MAYBE, Some("tests/fixtures/config.toml"));
+    assert_eq!(FIRST, "tests/fixtures/config.toml");
+    assert_eq!(
+        LOCALES,
+        ["tests/fixtures/i18n/de", "tests/fixtures/i18n/en"]
+    );
+}
+
+#[test]
+fn batches() {
+    const CONFIGS: [&str; 2] =
+        exists_all!["tests/fixtures/config.toml", "tests/fixtures/config.json"];
+    const OUTPUTS: [&str; 2] = valid_files!["tests/fixtures/out.csv", "tests/fixtures/out.txt"];
+    const DIRS: [&str; 1] = valid_dirs!["tests/fixtures/generated"];
+    // const MISSING: [&str; 2] = exists_all!["tests/fixtures/a.toml", "tests/fixtures/b.toml"];
+
+    assert_eq!(CONFIGS[1], "tests/fixtures/config.json");
+    assert_eq!(OUTPUTS.len(), 2);
+}
+
+#[test]
+fn snapshots() {
+    let recorded = fs_scout::snapshot::record("tests/fixtures/image").unwrap();
+    assert_eq!(
+        recorded,
+        "# fs-scout snapshot of \"tests/fixtures/image\"\n\
+         dir /etc\n\
+         dir /etc/app\n\
+         file /etc/app/config.toml\n\
+         dir /var\n\
+         dir /var/log\n\
+         file /var/log/app.log\n"
+    );
+
+    let lock = std::env::temp_dir().join(format!("fs-scout-{}.lock", std::process::id()));
+    fs_scout::snapshot::write("tests/fixtures/image", &lock).unwrap();
+    assert_eq!(std::fs::read_to_string(&lock).unwrap(), recorded);
+    std::fs::remove_file(lock).unwrap();
+
+    assert!(fs_scout::snapshot::record("tests/fixtures/missing").is_err());
+}